@@ -0,0 +1,237 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{fmt::Display, fs};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+
+/// Where answer hashes of teammates are kept, one file per teammate.
+pub const ANSWERS_DIR: &str = "src/answers";
+
+/// Header line identifying an answer hash file and the version of its format.
+const HEADER: &str = "# aoc answer hashes v2";
+
+/// Prefix of the headers of older formats, which cannot be compared with the current one.
+const HEADER_PREFIX: &str = "# aoc answer hashes";
+
+/// Argon2id memory cost in KiB and number of passes, the OWASP recommendation of 19 MiB and
+/// two passes. Each hash takes tens of milliseconds, see [`hash`].
+const ARGON2_MEMORY_KIB: u32 = 19 * 1024;
+const ARGON2_PASSES: u32 = 2;
+
+/// The salted hash of one answer. Entries refer to inputs by their hash, so answers are only
+/// compared when they were computed on the same input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    /// The input set the answer was computed on, for display only.
+    pub profile: String,
+    /// Hex encoded SHA-256 of the input.
+    pub input_sha256: String,
+    pub hash: String,
+}
+
+/// The answer hashes shared by one teammate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnswerHashes {
+    pub name: String,
+    /// Random hex string mixed into every hash, so answers cannot be looked up in a table
+    /// computed in advance.
+    pub salt: String,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Matches,
+    Differs,
+    /// The teammate has no answer for this part on the same input.
+    Unknown,
+}
+
+impl AnswerHashes {
+    pub fn new(name: &str, salt: &str) -> Self {
+        AnswerHashes {
+            name: name.into(),
+            salt: salt.into(),
+            entries: vec![],
+        }
+    }
+
+    pub fn add(&mut self, day: u8, part: u8, profile: &str, input_sha256: &str, answer: &str) {
+        self.entries.push(Entry {
+            day,
+            part,
+            profile: profile.into(),
+            input_sha256: input_sha256.into(),
+            hash: hash(&self.salt, input_sha256, day, part, answer),
+        });
+    }
+
+    /// Compares `answer` with the teammate's answer to the same part on the same input.
+    pub fn verify(&self, day: u8, part: u8, input_sha256: &str, answer: &str) -> Verdict {
+        let entry = self.entries.iter().find(|entry| {
+            entry.day == day && entry.part == part && entry.input_sha256 == input_sha256
+        });
+
+        match entry {
+            None => Verdict::Unknown,
+            Some(entry) if entry.hash == hash(&self.salt, input_sha256, day, part, answer) => {
+                Verdict::Matches
+            }
+            Some(_) => Verdict::Differs,
+        }
+    }
+
+    /// Parses the format written by [`AnswerHashes`]'s `Display` impl.
+    pub fn parse(val: &str) -> Result<Self, String> {
+        let mut lines = val.lines().map(str::trim).filter(|l| !l.is_empty());
+        match lines.next() {
+            Some(HEADER) => {}
+            Some(header) if header.starts_with(HEADER_PREFIX) => {
+                return Err("answer hashes of an older version, export them again".into())
+            }
+            _ => return Err("not an answer hash file".into()),
+        }
+
+        let mut field = |key: &str| {
+            lines
+                .next()
+                .and_then(|line| line.split_once('='))
+                .filter(|(k, _)| k.trim() == key)
+                .map(|(_, v)| v.trim().to_string())
+                .ok_or_else(|| format!("expected \"{key} = ...\""))
+        };
+        let name = field("name")?;
+        let salt = field("salt")?;
+
+        let entries = lines
+            .map(
+                |line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                    [day, part, profile, input_sha256, hash] => Ok(Entry {
+                        day: day
+                            .parse()
+                            .map_err(|_| format!("invalid day in \"{line}\""))?,
+                        part: part
+                            .parse()
+                            .map_err(|_| format!("invalid part in \"{line}\""))?,
+                        profile: profile.into(),
+                        input_sha256: input_sha256.into(),
+                        hash: hash.into(),
+                    }),
+                    _ => Err(format!("invalid entry \"{line}\"")),
+                },
+            )
+            .collect::<Result<_, _>>()?;
+
+        Ok(AnswerHashes {
+            name,
+            salt,
+            entries,
+        })
+    }
+}
+
+impl Display for AnswerHashes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        writeln!(f, "name = {}", self.name)?;
+        writeln!(f, "salt = {}", self.salt)?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{:02} {} {} {} {}",
+                entry.day, entry.part, entry.profile, entry.input_sha256, entry.hash
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Hashes `answer` with a deliberately slow key derivation function. The salt is shared along
+/// with the hashes, and answers are mostly short numbers, so a teammate could still try every
+/// plausible answer; the cost only makes that take hours instead of seconds.
+fn hash(salt: &str, input_sha256: &str, day: u8, part: u8, answer: &str) -> String {
+    let params = Params::new(ARGON2_MEMORY_KIB, ARGON2_PASSES, 1, Some(32))
+        .expect("argon2 parameters are valid");
+    let mut output = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(
+            answer.trim().as_bytes(),
+            format!("{salt}:{input_sha256}:{day}:{part}").as_bytes(),
+            &mut output,
+        )
+        .expect("argon2 accepts any answer and a salt of at least 8 bytes");
+    output.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub fn get_path(name: &str) -> String {
+    format!("{ANSWERS_DIR}/{name}.txt")
+}
+
+/// All answer hash files in [`ANSWERS_DIR`], sorted by name. Unreadable files are skipped.
+pub fn read_all() -> Vec<AnswerHashes> {
+    let Ok(entries) = fs::read_dir(ANSWERS_DIR) else {
+        return vec![];
+    };
+
+    let mut all: Vec<AnswerHashes> = entries
+        .filter_map(|entry| fs::read_to_string(entry.ok()?.path()).ok())
+        .filter_map(|content| AnswerHashes::parse(&content).ok())
+        .collect();
+
+    all.sort_by(|a, b| a.name.cmp(&b.name));
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "7d3f9b6284c6f36e77b425cac882e8fbbcc97a4727ec20790853076d0f463453";
+
+    fn hashes() -> AnswerHashes {
+        let mut hashes = AnswerHashes::new("bob", "0f1e2d");
+        hashes.add(1, 1, "default", INPUT, "24000");
+        hashes.add(1, 2, "alice", INPUT, "45000");
+        hashes
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let hashes = hashes();
+        assert_eq!(AnswerHashes::parse(&hashes.to_string()), Ok(hashes));
+        assert!(AnswerHashes::parse("name = bob").is_err());
+        assert_eq!(
+            AnswerHashes::parse("# aoc answer hashes v1\nname = bob\nsalt = 00\n"),
+            Err("answer hashes of an older version, export them again".into())
+        );
+        assert!(AnswerHashes::parse(&format!("{HEADER}\nname = bob\nsalt = 00\n01 1")).is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let hashes = hashes();
+        assert_eq!(hashes.verify(1, 1, INPUT, "24000"), Verdict::Matches);
+        assert_eq!(hashes.verify(1, 1, INPUT, "24000\n"), Verdict::Matches);
+        assert_eq!(hashes.verify(1, 2, INPUT, "45001"), Verdict::Differs);
+        assert_eq!(hashes.verify(2, 1, INPUT, "24000"), Verdict::Unknown);
+        assert_eq!(hashes.verify(1, 1, "other", "24000"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_hash_hides_answer() {
+        let hashes = hashes();
+        assert!(!hashes.to_string().contains("24000"));
+        assert_ne!(
+            hash("0f1e2d", INPUT, 1, 1, "24000"),
+            hash("a1b2c3", INPUT, 1, 1, "24000")
+        );
+        assert_ne!(
+            hash("0f1e2d", INPUT, 1, 1, "24000"),
+            hash("0f1e2d", INPUT, 1, 2, "24000")
+        );
+    }
+}
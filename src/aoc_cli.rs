@@ -0,0 +1,865 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs::{self, create_dir_all},
+    io::{self, Write},
+    path::Path,
+    process::{self, Command, ExitStatus, Output, Stdio},
+    sync::LazyLock,
+    time::SystemTime,
+};
+
+use regex::Regex;
+
+use crate::calendar;
+use crate::profile;
+use crate::provenance::{self, Metadata, Status};
+use crate::site::{self, Client, SiteError, SubmitOutcome};
+
+#[derive(Debug)]
+pub enum AocCliError {
+    CommandNotFound(io::Error),
+    CommandNotCallable(io::Error),
+    UnknownVersion(String),
+    UnsupportedVersion(Version),
+    BadExitStatus {
+        status: ExitStatus,
+        stderr: String,
+        failure: Failure,
+    },
+    IoError {
+        path: String,
+        source: io::Error,
+    },
+    /// The input was changed since it was downloaded, so it is not overwritten.
+    ModifiedInput {
+        path: String,
+    },
+    /// A request of the built-in client failed, see [`Backend::Site`].
+    Site(SiteError),
+}
+
+/// Known reasons for aoc-cli to fail, recognized from its error output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Failure {
+    MissingSession,
+    ExpiredSession,
+    NotUnlocked,
+    NotFound,
+    RateLimited,
+    Unknown,
+}
+
+/// Only reads numbers as status codes where an HTTP status is reported, e.g.
+/// "status client error (400 bad request)", not in years or byte counts.
+static HTTP_STATUS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:status|\()\D{0,20}?\b(\d{3})\b").unwrap());
+
+impl Failure {
+    /// Classifies a failed aoc-cli call from what it printed to stderr.
+    pub fn classify(stderr: &str) -> Self {
+        let stderr = stderr.to_lowercase();
+        let mentions = |needles: &[&str]| needles.iter().any(|n| stderr.contains(n));
+        let status = HTTP_STATUS
+            .captures(&stderr)
+            .map(|captures| captures[1].to_string());
+        let status_is = |codes: &[&str]| status.as_deref().is_some_and(|s| codes.contains(&s));
+
+        if mentions(&["session cookie", "session file"])
+            && mentions(&[
+                "no such file",
+                "not found",
+                "failed to read",
+                "could not read",
+            ])
+        {
+            Failure::MissingSession
+        } else if status_is(&["429"]) || mentions(&["too many requests"]) {
+            Failure::RateLimited
+        } else if mentions(&["still locked", "not unlocked", "unlocks"]) {
+            Failure::NotUnlocked
+        } else if status_is(&["400", "401"]) || mentions(&["bad request", "log in", "unauthorized"])
+        {
+            Failure::ExpiredSession
+        } else if status_is(&["404"]) || mentions(&["not found"]) {
+            Failure::NotFound
+        } else {
+            Failure::Unknown
+        }
+    }
+
+    /// A suggestion for how to resolve the failure, if there is one.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Failure::MissingSession => Some("Run \"cargo session set\" with the session cookie from adventofcode.com, see the README for how to find it."),
+            Failure::ExpiredSession => Some("The session cookie was rejected, it probably expired. Log in to adventofcode.com again and run \"cargo session set\" with the new cookie."),
+            Failure::NotUnlocked => Some("This puzzle has not been unlocked yet. Puzzles unlock at midnight US Eastern time (UTC-5)."),
+            Failure::NotFound => Some("The puzzle does not exist. Check the day and --year, future days are not available yet."),
+            Failure::RateLimited => Some("adventofcode.com is rate limiting requests. Wait a few minutes before trying again."),
+            Failure::Unknown => None,
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::MissingSession => write!(f, "no session cookie found"),
+            Failure::ExpiredSession => write!(f, "session cookie expired or invalid"),
+            Failure::NotUnlocked => write!(f, "puzzle not unlocked yet"),
+            Failure::NotFound => write!(f, "puzzle not found"),
+            Failure::RateLimited => write!(f, "rate limited"),
+            Failure::Unknown => write!(f, "unknown error"),
+        }
+    }
+}
+
+impl AocCliError {
+    /// Why the site refused a request, if it did.
+    pub fn failure(&self) -> Option<Failure> {
+        match self {
+            AocCliError::BadExitStatus { failure, .. } => Some(*failure),
+            AocCliError::Site(e) => Some(e.failure()),
+            _ => None,
+        }
+    }
+
+    /// A suggestion for how to resolve the error, if there is one.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            AocCliError::CommandNotFound(_) | AocCliError::UnsupportedVersion(_) => Some(format!(
+                "Try running \"cargo install aoc-cli --version {RECOMMENDED}\" to install a supported version."
+            )),
+            AocCliError::BadExitStatus { failure, .. } => failure.hint().map(String::from),
+            AocCliError::Site(e) => e.failure().hint().map(String::from),
+            AocCliError::IoError { path, .. } => {
+                Some(format!("Check that \"{path}\" is writable."))
+            }
+            AocCliError::ModifiedInput { .. } => {
+                Some("Pass --force to overwrite it with a fresh download.".into())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for AocCliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocCliError::CommandNotFound(_) => write!(f, "aoc-cli is not present in environment."),
+            AocCliError::CommandNotCallable(_) => write!(f, "aoc-cli could not be called."),
+            AocCliError::UnknownVersion(output) => {
+                write!(f, "could not read aoc-cli version from \"{output}\".")
+            }
+            AocCliError::UnsupportedVersion(version) => write!(
+                f,
+                "aoc-cli {version} is not supported, versions {MIN_SUPPORTED} up to {}.{}.x are.",
+                NEWEST_TESTED.major, NEWEST_TESTED.minor
+            ),
+            AocCliError::BadExitStatus {
+                status, failure, ..
+            } => match failure {
+                Failure::Unknown => write!(f, "aoc-cli exited with a non-zero status ({status})."),
+                failure => write!(
+                    f,
+                    "aoc-cli exited with a non-zero status ({status}): {failure}."
+                ),
+            },
+            AocCliError::IoError { path, .. } => {
+                write!(f, "could not write output files to \"{path}\".")
+            }
+            AocCliError::ModifiedInput { path } => {
+                write!(f, "input \"{path}\" was modified since it was downloaded.")
+            }
+            AocCliError::Site(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for AocCliError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocCliError::CommandNotFound(e)
+            | AocCliError::CommandNotCallable(e)
+            | AocCliError::IoError { source: e, .. } => Some(e),
+            AocCliError::Site(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<SiteError> for AocCliError {
+    fn from(e: SiteError) -> Self {
+        AocCliError::Site(e)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Version {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parses the output of `aoc -V`, e.g. `aoc-cli 0.12.0`.
+    pub fn parse(output: &str) -> Option<Self> {
+        let version = output.split_whitespace().last()?;
+        let version = version.split(['-', '+']).next()?;
+        let mut parts = version.split('.').map(|p| p.parse::<u32>());
+        let version = Version::new(
+            parts.next()?.ok()?,
+            parts.next()?.ok()?,
+            parts.next()?.ok()?,
+        );
+        match parts.next() {
+            Some(_) => None,
+            None => Some(version),
+        }
+    }
+
+    /// Selects the argument layout this version expects. Versions newer than
+    /// [`NEWEST_TESTED`] are assumed to take the same arguments, see [`Version::is_tested`].
+    pub fn layout(&self) -> Result<Layout, AocCliError> {
+        match *self {
+            v if v < MIN_SUPPORTED => Err(AocCliError::UnsupportedVersion(v)),
+            v if v < COMMAND_FIRST_SINCE => Ok(Layout::CommandLast),
+            _ => Ok(Layout::CommandFirst),
+        }
+    }
+
+    /// Whether this version is within the tested range, patch releases of [`NEWEST_TESTED`]
+    /// included.
+    pub fn is_tested(&self) -> bool {
+        *self >= MIN_SUPPORTED
+            && (self.major, self.minor) <= (NEWEST_TESTED.major, NEWEST_TESTED.minor)
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+pub const MIN_SUPPORTED: Version = Version::new(0, 7, 0);
+/// The newest release the argument layouts were tested with, see [`Version::is_tested`].
+pub const NEWEST_TESTED: Version = Version::new(0, 12, 0);
+pub const RECOMMENDED: Version = Version::new(0, 12, 0);
+const COMMAND_FIRST_SINCE: Version = Version::new(0, 12, 0);
+/// The first release whose `download` takes `--puzzle-only`, see [`refresh_args`].
+const PUZZLE_ONLY_SINCE: Version = Version::new(0, 12, 0);
+
+/// Environment variable aoc-cli reads the session cookie from.
+const AOC_CLI_SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+
+/// Argument layouts of the supported aoc-cli releases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// aoc-cli 0.7 - 0.11: options first, the command last, e.g. `--day 1 download`.
+    CommandLast,
+    /// aoc-cli 0.12 and later: the command first, followed by its options, e.g. `download --day 1`.
+    CommandFirst,
+}
+
+/// Environment variable naming the aoc-cli program to call instead of `aoc` on the `PATH`,
+/// e.g. a wrapper script or a stand-in for testing.
+pub const PROGRAM_ENV: &str = "AOC_CLI";
+pub const DEFAULT_PROGRAM: &str = "aoc";
+
+/// An installed aoc-cli program and its version.
+#[derive(Clone, Debug)]
+pub struct AocCli {
+    pub program: String,
+    pub version: Version,
+}
+
+/// The aoc-cli program configured via [`PROGRAM_ENV`].
+pub fn program() -> String {
+    env::var(PROGRAM_ENV)
+        .ok()
+        .filter(|program| !program.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PROGRAM.into())
+}
+
+/// Checks that aoc-cli is installed, see [`check_program`].
+pub fn check() -> Result<AocCli, AocCliError> {
+    check_program(&program())
+}
+
+/// Checks that `program` can be called and is a supported version of aoc-cli.
+pub fn check_program(program: &str) -> Result<AocCli, AocCliError> {
+    let output = Command::new(program)
+        .arg("-V")
+        .output()
+        .map_err(AocCliError::CommandNotFound)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version =
+        Version::parse(&stdout).ok_or_else(|| AocCliError::UnknownVersion(stdout.trim().into()))?;
+
+    version.layout()?;
+    if !version.is_tested() {
+        eprintln!(
+            "warning: aoc-cli {version} is newer than the newest tested version {}.{}.x, assuming it takes the same arguments. Install version {RECOMMENDED} if commands fail.",
+            NEWEST_TESTED.major, NEWEST_TESTED.minor
+        );
+    }
+    Ok(AocCli {
+        program: program.into(),
+        version,
+    })
+}
+
+/// Where puzzles are fetched from: aoc-cli, or the built-in client if a different site is set
+/// via [`site::URL_ENV`], since aoc-cli always talks to adventofcode.com.
+pub enum Backend {
+    AocCli(AocCli),
+    Site(Client),
+}
+
+impl Backend {
+    pub fn detect() -> Result<Self, AocCliError> {
+        if site::url_override().is_some() {
+            return Ok(Backend::Site(Client::from_env()?));
+        }
+        check().map(Backend::AocCli)
+    }
+}
+
+pub fn read(backend: &Backend, day: u8, year: Option<u16>) -> Result<(), AocCliError> {
+    // TODO: output local puzzle if present.
+    match backend {
+        Backend::AocCli(cli) => {
+            let args = build_args(cli.version.layout()?, "read", &session_args(), day, year);
+            check_status(call_aoc_cli(cli, &args)?)?;
+        }
+        Backend::Site(client) => {
+            let year = year.unwrap_or_else(|| calendar::current_event_year(SystemTime::now()));
+            print!("{}", client.puzzle(year, day)?);
+        }
+    }
+    Ok(())
+}
+
+/// Downloads input and puzzle of `day` and records the input's [`provenance`].
+/// An existing input that was modified is only replaced if `force` is set, one without metadata
+/// is replaced with a warning.
+pub fn download(
+    backend: &Backend,
+    day: u8,
+    year: Option<u16>,
+    force: bool,
+) -> Result<(), AocCliError> {
+    let input_path = get_input_path(day);
+
+    match provenance::status(day) {
+        Status::Modified if !force => {
+            return Err(AocCliError::ModifiedInput { path: input_path });
+        }
+        // inputs downloaded before metadata was recorded have none.
+        Status::Untracked if !force => {
+            eprintln!("warning: input \"{input_path}\" has no download metadata, replacing it.")
+        }
+        _ => {}
+    }
+
+    let puzzle_path = get_puzzle_path(day);
+    for path in [&input_path, &puzzle_path] {
+        let dir = Path::new(path).parent().unwrap();
+        create_dir_all(dir).map_err(|source| AocCliError::IoError {
+            path: dir.display().to_string(),
+            source,
+        })?;
+    }
+
+    // resolve the year here instead of leaving it to aoc-cli, so it can be recorded.
+    let year = year.unwrap_or_else(|| calendar::current_event_year(SystemTime::now()));
+
+    match backend {
+        Backend::AocCli(cli) => {
+            let args = build_args(
+                cli.version.layout()?,
+                "download",
+                &[download_args(&input_path, &puzzle_path), session_args()].concat(),
+                day,
+                Some(year),
+            );
+            check_status(call_aoc_cli(cli, &args)?)?;
+        }
+        Backend::Site(client) => {
+            let input = client.input(year, day)?;
+            let puzzle = client.puzzle(year, day)?;
+            write_file(&input_path, &input)?;
+            write_file(&puzzle_path, &puzzle)?;
+        }
+    }
+
+    let metadata_path = provenance::get_metadata_path(day);
+    fs::read(&input_path)
+        .map(|input| Metadata::new(year, day, &input, &provenance::account()))
+        .and_then(|metadata| provenance::write(&metadata))
+        .map_err(|source| AocCliError::IoError {
+            path: metadata_path.clone(),
+            source,
+        })?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    println!("🎄 Successfully wrote metadata to \"{}\".", &metadata_path);
+    Ok(())
+}
+
+/// Submits `answer` to `part` of `day`.
+pub fn submit(
+    backend: &Backend,
+    day: u8,
+    year: Option<u16>,
+    part: u8,
+    answer: &str,
+) -> Result<SubmitOutcome, AocCliError> {
+    let year = year.unwrap_or_else(|| calendar::current_event_year(SystemTime::now()));
+
+    match backend {
+        Backend::AocCli(cli) => {
+            let mut args = build_args(
+                cli.version.layout()?,
+                "submit",
+                &session_args(),
+                day,
+                Some(year),
+            );
+            args.extend([part.to_string(), answer.to_string()]);
+            let output = check_status(call_aoc_cli(cli, &args)?)?;
+            Ok(SubmitOutcome::classify(&String::from_utf8_lossy(
+                &output.stdout,
+            )))
+        }
+        Backend::Site(client) => Ok(client.submit(year, day, part, answer)?),
+    }
+}
+
+/// Downloads the puzzle of `day` again, e.g. to record a new answer. The input is left alone.
+pub fn refresh_puzzle(backend: &Backend, day: u8, year: Option<u16>) -> Result<(), AocCliError> {
+    let year = year.unwrap_or_else(|| calendar::current_event_year(SystemTime::now()));
+    let puzzle_path = get_puzzle_path(day);
+
+    match backend {
+        Backend::AocCli(cli) => {
+            let scratch_input = scratch_path(&format!("refresh-{day:02}.txt"));
+            let args = build_args(
+                cli.version.layout()?,
+                "download",
+                &[
+                    refresh_args(cli.version, &puzzle_path, &scratch_input),
+                    session_args(),
+                ]
+                .concat(),
+                day,
+                Some(year),
+            );
+            let result = call_aoc_cli(cli, &args);
+            fs::remove_file(&scratch_input).ok();
+            check_status(result?)?;
+        }
+        Backend::Site(client) => write_file(&puzzle_path, &client.puzzle(year, day)?)?,
+    }
+
+    println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\".");
+    Ok(())
+}
+
+/// Checks that the site accepts the session cookie, by downloading an input that only a logged
+/// in user can see. aoc-cli writes it to scratch files, which are removed again.
+pub fn check_session(backend: &Backend) -> Result<(), AocCliError> {
+    match backend {
+        Backend::AocCli(cli) => {
+            let day = calendar::FIRST_DAY;
+            let year = calendar::current_event_year(SystemTime::now());
+            let scratch_input = scratch_path("check-session.txt");
+            let scratch_puzzle = scratch_path("check-session.md");
+            let args = build_args(
+                cli.version.layout()?,
+                "download",
+                &[
+                    download_args(&scratch_input, &scratch_puzzle),
+                    session_args(),
+                ]
+                .concat(),
+                day,
+                Some(year),
+            );
+            let result = call_aoc_cli(cli, &args);
+            fs::remove_file(&scratch_input).ok();
+            fs::remove_file(&scratch_puzzle).ok();
+            check_status(result?)?;
+            Ok(())
+        }
+        Backend::Site(client) => Ok(client.check_session()?),
+    }
+}
+
+/// A file for aoc-cli to write what is not kept, unique to this process.
+fn scratch_path(name: &str) -> String {
+    env::temp_dir()
+        .join(format!("aoc-{}-{name}", process::id()))
+        .display()
+        .to_string()
+}
+
+fn write_file(path: &str, content: &str) -> Result<(), AocCliError> {
+    let dir = Path::new(path).parent().unwrap();
+    create_dir_all(dir)
+        .and_then(|_| fs::write(path, content))
+        .map_err(|source| AocCliError::IoError {
+            path: path.into(),
+            source,
+        })
+}
+
+/// A day counts as downloaded once both its puzzle and a non-empty input are on disk.
+/// `scaffold` creates empty input files, so those do not count.
+pub fn is_downloaded(day: u8) -> bool {
+    let has_input = fs::metadata(get_input_path(day)).is_ok_and(|m| m.len() > 0);
+    has_input && Path::new(&get_puzzle_path(day)).exists()
+}
+
+/// Input of `day` in the input set of the current profile, see [`profile::current`].
+pub fn get_input_path(day: u8) -> String {
+    get_profile_input_path(day, profile::current().as_deref())
+}
+
+/// Puzzle of `day` as seen by the current profile. Puzzles record the answers of the
+/// account they were downloaded with, so every profile keeps its own copy.
+pub fn get_puzzle_path(day: u8) -> String {
+    get_profile_puzzle_path(day, profile::current().as_deref())
+}
+
+pub fn get_profile_input_path(day: u8, profile: Option<&str>) -> String {
+    let day_padded = format!("{day:02}");
+    format!(
+        "{}/{day_padded}.txt",
+        profile::dir(profile::INPUTS_DIR, profile)
+    )
+}
+
+pub fn get_profile_puzzle_path(day: u8, profile: Option<&str>) -> String {
+    let day_padded = format!("{day:02}");
+    format!(
+        "{}/{day_padded}.md",
+        profile::dir(profile::PUZZLES_DIR, profile)
+    )
+}
+
+/// Points aoc-cli to the session cookie of the current profile, if one is selected. A cookie
+/// set via [`site::SESSION_ENV`] is passed on in [`call_aoc_cli`] instead.
+fn session_args() -> Vec<String> {
+    if site::session_override().is_some() {
+        return vec![];
+    }
+    profile::current()
+        .and_then(|profile| profile::session_file(&profile))
+        .map(|path| vec!["--session-file".into(), path.display().to_string()])
+        .unwrap_or_default()
+}
+
+fn download_args(input_path: &str, puzzle_path: &str) -> Vec<String> {
+    vec![
+        "--overwrite".into(),
+        "--input-file".into(),
+        input_path.into(),
+        "--puzzle-file".into(),
+        puzzle_path.into(),
+    ]
+}
+
+/// Options to download only the puzzle to `puzzle_path`. Releases before [`PUZZLE_ONLY_SINCE`]
+/// always write the input as well, so it goes to `scratch_input` instead of the one in use.
+fn refresh_args(version: Version, puzzle_path: &str, scratch_input: &str) -> Vec<String> {
+    if version < PUZZLE_ONLY_SINCE {
+        return download_args(scratch_input, puzzle_path);
+    }
+    vec![
+        "--overwrite".into(),
+        "--puzzle-only".into(),
+        "--puzzle-file".into(),
+        puzzle_path.into(),
+    ]
+}
+
+fn build_args(
+    layout: Layout,
+    command: &str,
+    args: &[String],
+    day: u8,
+    year: Option<u16>,
+) -> Vec<String> {
+    let mut cmd_args = Vec::with_capacity(args.len() + 5);
+
+    if layout == Layout::CommandFirst {
+        cmd_args.push(command.into());
+    }
+
+    cmd_args.extend_from_slice(args);
+
+    if let Some(year) = year {
+        cmd_args.push("--year".into());
+        cmd_args.push(year.to_string());
+    }
+
+    cmd_args.push("--day".into());
+    cmd_args.push(day.to_string());
+
+    if layout == Layout::CommandLast {
+        cmd_args.push(command.into());
+    }
+
+    cmd_args
+}
+
+fn call_aoc_cli(cli: &AocCli, args: &[String]) -> Result<Output, AocCliError> {
+    if cfg!(debug_assertions) {
+        println!("Calling >{} with: {}", cli.program, args.join(" "));
+    }
+
+    // output is captured to classify failures and responses, and passed on to the user afterwards.
+    let mut cmd = Command::new(&cli.program);
+    if let Some(session) = site::session_override() {
+        cmd.env(AOC_CLI_SESSION_ENV, session);
+    }
+    let output = cmd
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(AocCliError::CommandNotCallable)?;
+
+    io::stdout().write_all(&output.stdout).ok();
+    io::stderr().write_all(&output.stderr).ok();
+    Ok(output)
+}
+
+fn check_status(output: Output) -> Result<Output, AocCliError> {
+    if output.status.success() {
+        return Ok(output);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    Err(AocCliError::BadExitStatus {
+        status: output.status,
+        failure: Failure::classify(&stderr),
+        stderr,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(version: &str) -> Option<Layout> {
+        Version::parse(version).unwrap().layout().ok()
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            Version::parse("aoc-cli 0.7.0\n"),
+            Some(Version::new(0, 7, 0))
+        );
+        assert_eq!(
+            Version::parse("aoc-cli 0.12.0"),
+            Some(Version::new(0, 12, 0))
+        );
+        assert_eq!(Version::parse("0.12.1-beta"), Some(Version::new(0, 12, 1)));
+        assert_eq!(Version::parse("aoc-cli"), None);
+        assert_eq!(Version::parse("aoc-cli 0.12"), None);
+        assert_eq!(Version::parse("aoc-cli 0.12.0.1"), None);
+        assert_eq!(Version::parse(""), None);
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(layout("aoc-cli 0.5.0"), None);
+        assert_eq!(layout("aoc-cli 0.7.0"), Some(Layout::CommandLast));
+        assert_eq!(layout("aoc-cli 0.11.3"), Some(Layout::CommandLast));
+        assert_eq!(layout("aoc-cli 0.12.0"), Some(Layout::CommandFirst));
+        assert_eq!(layout("aoc-cli 1.0.0"), Some(Layout::CommandFirst));
+    }
+
+    #[test]
+    fn test_is_tested() {
+        let tested = |version| Version::parse(version).unwrap().is_tested();
+        assert!(!tested("aoc-cli 0.6.9"));
+        assert!(tested("aoc-cli 0.7.0"));
+        assert!(tested("aoc-cli 0.12.0"));
+        assert!(tested("aoc-cli 0.12.7"));
+        assert!(!tested("aoc-cli 0.13.0"));
+        assert!(!tested("aoc-cli 0.99.0"));
+        assert!(!tested("aoc-cli 1.0.0"));
+    }
+
+    #[test]
+    fn test_classify_failure() {
+        assert_eq!(
+            Failure::classify("error: Failed to read session cookie from \"/home/elf/.adventofcode.session\": No such file or directory (os error 2)"),
+            Failure::MissingSession
+        );
+        assert_eq!(
+            Failure::classify("Error: HTTP status client error (400 Bad Request) for url (https://adventofcode.com/2022/day/1/input)"),
+            Failure::ExpiredSession
+        );
+        assert_eq!(
+            Failure::classify("error: Puzzle 25 of 2022 is still locked"),
+            Failure::NotUnlocked
+        );
+        assert_eq!(
+            Failure::classify("Error: HTTP status client error (404 Not Found) for url (https://adventofcode.com/2022/day/26)"),
+            Failure::NotFound
+        );
+        assert_eq!(
+            Failure::classify("Error: HTTP status client error (429 Too Many Requests)"),
+            Failure::RateLimited
+        );
+        assert_eq!(
+            Failure::classify("thread 'main' panicked"),
+            Failure::Unknown
+        );
+        assert_eq!(Failure::classify(""), Failure::Unknown);
+    }
+
+    #[test]
+    fn test_classify_status_codes() {
+        assert_eq!(
+            Failure::classify("error: status 401"),
+            Failure::ExpiredSession
+        );
+        assert_eq!(
+            Failure::classify("Error: HTTP status code: 404"),
+            Failure::NotFound
+        );
+        // digits outside of a status are not status codes.
+        assert_eq!(
+            Failure::classify("error: puzzle 4001 of year 2400 failed"),
+            Failure::Unknown
+        );
+        assert_eq!(
+            Failure::classify("error: read 400 bytes, expected 429"),
+            Failure::Unknown
+        );
+        assert_eq!(
+            Failure::classify("error: status 4001 (invalid)"),
+            Failure::Unknown
+        );
+    }
+
+    #[test]
+    fn test_error_hint() {
+        let err = AocCliError::BadExitStatus {
+            status: ExitStatus::default(),
+            stderr: "Error: 429 Too Many Requests".into(),
+            failure: Failure::RateLimited,
+        };
+        assert!(err.hint().unwrap().contains("Wait a few minutes"));
+        assert!(err.to_string().contains("rate limited"));
+
+        let err = AocCliError::CommandNotFound(io::Error::from(io::ErrorKind::NotFound));
+        assert!(err.hint().unwrap().contains("cargo install aoc-cli"));
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn test_build_args_command_last() {
+        assert_eq!(
+            build_args(Layout::CommandLast, "read", &[], 1, None),
+            vec!["--day", "1", "read"]
+        );
+        assert_eq!(
+            build_args(
+                Layout::CommandLast,
+                "download",
+                &download_args("src/inputs/01.txt", "src/puzzles/01.md"),
+                1,
+                Some(2020)
+            ),
+            vec![
+                "--overwrite",
+                "--input-file",
+                "src/inputs/01.txt",
+                "--puzzle-file",
+                "src/puzzles/01.md",
+                "--year",
+                "2020",
+                "--day",
+                "1",
+                "download"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_args_command_first() {
+        assert_eq!(
+            build_args(Layout::CommandFirst, "read", &[], 25, None),
+            vec!["read", "--day", "25"]
+        );
+        assert_eq!(
+            build_args(
+                Layout::CommandFirst,
+                "download",
+                &download_args("src/inputs/01.txt", "src/puzzles/01.md"),
+                1,
+                Some(2020)
+            ),
+            vec![
+                "download",
+                "--overwrite",
+                "--input-file",
+                "src/inputs/01.txt",
+                "--puzzle-file",
+                "src/puzzles/01.md",
+                "--year",
+                "2020",
+                "--day",
+                "1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_refresh_args() {
+        assert_eq!(
+            refresh_args(Version::new(0, 12, 0), "src/puzzles/01.md", "/tmp/01.txt"),
+            vec![
+                "--overwrite",
+                "--puzzle-only",
+                "--puzzle-file",
+                "src/puzzles/01.md"
+            ]
+        );
+        // older releases cannot skip the input, it must not replace the one in use.
+        assert_eq!(
+            refresh_args(Version::new(0, 11, 3), "src/puzzles/01.md", "/tmp/01.txt"),
+            vec![
+                "--overwrite",
+                "--input-file",
+                "/tmp/01.txt",
+                "--puzzle-file",
+                "src/puzzles/01.md"
+            ]
+        );
+    }
+}
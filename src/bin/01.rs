@@ -1,16 +1,22 @@
-pub fn part_one(input: &str) -> Option<u32> {
-    let cals = input
-        .split("\n\n")
-        .map(|e| e.lines().map(|c| c.parse::<u32>().unwrap()).sum::<u32>())
-        .max();
-    cals
+use advent_of_code::helpers::parse::{blocks, parse_field, parse_lines};
+use advent_of_code::helpers::ParseError;
+
+pub fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+    blocks(input)
+        .map(|e| {
+            parse_lines(e, |line| parse_field::<u32>(line, line))
+                .map(|cals| cals.into_iter().sum::<u32>())
+                .map_err(|err| err.within(input, e))
+        })
+        .collect()
+}
+
+pub fn part_one(cals: &[u32]) -> Option<u32> {
+    cals.iter().copied().max()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut cals = input
-        .split("\n\n")
-        .map(|e| e.lines().map(|c| c.parse::<u32>().unwrap()).sum::<u32>())
-        .collect::<Vec<u32>>();
+pub fn part_two(cals: &[u32]) -> Option<u32> {
+    let mut cals = cals.to_vec();
     cals.sort();
     let top3: u32 = cals.into_iter().rev().take(3).sum();
     Some(top3)
@@ -18,8 +24,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 1);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve_parsed!(parse, part_one, part_two, input);
 }
 
 #[cfg(test)]
@@ -29,12 +34,20 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 1);
-        assert_eq!(part_one(&input), Some(24_000));
+        assert_eq!(part_one(&parse(&input).unwrap()), Some(24_000));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 1);
-        assert_eq!(part_two(&input), Some(45_000));
+        assert_eq!(part_two(&parse(&input).unwrap()), Some(45_000));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse("1000\n2000\n\n4000\n5x00\n").unwrap_err().to_string(),
+            "input:5:1: expected `u32`, found '5x00'"
+        );
     }
 }
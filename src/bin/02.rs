@@ -1,3 +1,5 @@
+use advent_of_code::helpers::parse::{char_in, fields, parse_lines};
+use advent_of_code::helpers::ParseError;
 use std::char;
 
 #[derive(Debug, PartialEq)]
@@ -21,7 +23,7 @@ fn match_rps(c: char) -> RockPaperScissors {
         'X' => RockPaperScissors::Rock,
         'Y' => RockPaperScissors::Paper,
         'Z' => RockPaperScissors::Scissors,
-        _ => panic!("Invalid input"),
+        _ => unreachable!("checked by parse_round"),
     }
 }
 
@@ -30,7 +32,7 @@ fn match_result(c: char) -> RockPaperScissorsResult {
         'Z' => RockPaperScissorsResult::Win,
         'X' => RockPaperScissorsResult::Lose,
         'Y' => RockPaperScissorsResult::Draw,
-        _ => panic!("Invalid input"),
+        _ => unreachable!("checked by parse_round"),
     }
 }
 
@@ -75,21 +77,33 @@ fn parse_moves_part2(moves: (RockPaperScissors, RockPaperScissorsResult)) -> u32
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    input
-        .lines()
-        .map(|l| l.replace(' ', ""))
-        .map(|l| l.chars().collect::<Vec<char>>())
+type Round = (char, char);
+
+fn parse_round(line: &str) -> Result<Round, ParseError> {
+    let fields = fields("{} {}", line)?;
+    Ok((
+        char_in(line, fields[0], 'A'..='C')?,
+        char_in(line, fields[1], 'X'..='Z')?,
+    ))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Round>, ParseError> {
+    parse_lines(input, parse_round)
+}
+
+pub fn part_one(rounds: &[Round]) -> Option<u32> {
+    rounds
+        .iter()
+        .map(|&(a, b)| vec![a, b])
         .map(match_chars)
         .map(parse_moves)
         .reduce(|a, b| a + b)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    input
-        .lines()
-        .map(|l| l.replace(' ', ""))
-        .map(|l| l.chars().collect::<Vec<char>>())
+pub fn part_two(rounds: &[Round]) -> Option<u32> {
+    rounds
+        .iter()
+        .map(|&(a, b)| vec![a, b])
         .map(match_chars_part2)
         .map(parse_moves_part2)
         .reduce(|a, b| a + b)
@@ -97,8 +111,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 2);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve_parsed!(parse, part_one, part_two, input);
 }
 
 #[cfg(test)]
@@ -221,15 +234,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("A Y\nB X\n"), Ok(vec![('A', 'Y'), ('B', 'X')]));
+        assert_eq!(
+            parse("A Y\nD X\n").unwrap_err().to_string(),
+            "input:2:1: expected 'A'..'C', found 'D'"
+        );
+        assert_eq!(
+            parse("A Y\nB XY\n").unwrap_err().to_string(),
+            "input:2:3: expected 'X'..'Z', found 'XY'"
+        );
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 2);
-        assert_eq!(part_one(&input), Some(15));
+        assert_eq!(part_one(&parse(&input).unwrap()), Some(15));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 2);
-        assert_eq!(part_two(&input), Some(12));
+        assert_eq!(part_two(&parse(&input).unwrap()), Some(12));
     }
 }
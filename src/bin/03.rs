@@ -1,6 +1,7 @@
+use advent_of_code::helpers::parse::parse_lines;
+use advent_of_code::helpers::ParseError;
 use std::char;
 use std::collections::HashSet;
-use std::u32;
 // use std::collections::HashMap;
 
 /*
@@ -12,16 +13,16 @@ fn priority() -> HashMap<char, u8> {
         .collect::<HashMap<char, u8>>()
 }
 */
-const fn priority(ch: &char) -> u8 {
+fn priority(ch: &char) -> u8 {
     match ch {
         'a'..='z' => *ch as u8 - b'a' + 1,
         'A'..='Z' => *ch as u8 - b'A' + 27,
-        _ => panic!("Invalid character"),
+        _ => unreachable!("checked by parse_rucksack"),
     }
 }
 
 fn split_string(input: &str) -> (&str, &str) {
-    assert!(input.len() % 2 == 0);
+    assert!(input.len().is_multiple_of(2));
     let mid = input.len() / 2;
     input.split_at(mid)
 }
@@ -55,19 +56,51 @@ fn priorities_sum_part2(v: Vec<&str>) -> u32 {
     priorities_sum(common_chars)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
+/// A rucksack holds items `a`..`z` and `A`..`Z`, split evenly between two compartments.
+fn parse_rucksack(line: &str) -> Result<String, ParseError> {
+    if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        return Err(ParseError::at(
+            line,
+            &line[i..i + c.len_utf8()],
+            "'a'..'z' or 'A'..'Z'",
+        ));
+    }
+    if !line.len().is_multiple_of(2) {
+        return Err(ParseError::at(line, line, "an even number of items"));
+    }
+    Ok(line.to_string())
+}
+
+/// Rucksacks come in groups of three, one group per elf badge.
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    let rucksacks = parse_lines(input, parse_rucksack)?;
+    if !rucksacks.len().is_multiple_of(3) {
+        let incomplete = input
             .lines()
-            .map(split_string)
+            .nth(rucksacks.len() / 3 * 3)
+            .unwrap_or_default();
+        return Err(ParseError::at(
+            input,
+            incomplete,
+            "a complete group of three rucksacks",
+        ));
+    }
+    Ok(rucksacks)
+}
+
+pub fn part_one(rucksacks: &[String]) -> Option<u32> {
+    Some(
+        rucksacks
+            .iter()
+            .map(|r| split_string(r))
             .map(|(a, b)| common_chars(a, b))
             .map(priorities_sum)
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let v: Vec<&str> = input.lines().collect();
+pub fn part_two(rucksacks: &[String]) -> Option<u32> {
+    let v: Vec<&str> = rucksacks.iter().map(String::as_str).collect();
     v.chunks(3)
         .map(|v| priorities_sum_part2(v.to_vec()))
         .sum::<u32>()
@@ -77,8 +110,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 3);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve_parsed!(parse, part_one, part_two, input);
 }
 
 #[cfg(test)]
@@ -141,15 +173,36 @@ mod tests {
         assert_eq!(priorities_sum(vec!['a', 'A']), 28);
         assert_eq!(priorities_sum(vec!['A', 'a']), 28);
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNq1jqz\n")
+                .unwrap_err()
+                .to_string(),
+            "input:2:7: expected 'a'..'z' or 'A'..'Z', found '1'"
+        );
+        assert_eq!(
+            parse("abc\n").unwrap_err().to_string(),
+            "input:1:1: expected an even number of items, found 'abc'"
+        );
+        assert_eq!(
+            parse("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nab\n")
+                .unwrap_err()
+                .to_string(),
+            "input:4:1: expected a complete group of three rucksacks, found 'ab'"
+        );
+    }
+
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 3);
-        assert_eq!(part_one(&input), Some(157));
+        assert_eq!(part_one(&parse(&input).unwrap()), Some(157));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 3);
-        assert_eq!(part_two(&input), Some(70));
+        assert_eq!(part_two(&parse(&input).unwrap()), Some(70));
     }
 }
@@ -1,61 +1,53 @@
-use std::collections::HashSet;
-use std::convert::TryFrom;
+use advent_of_code::helpers::parse::{fields, parse_lines, pattern};
+use advent_of_code::helpers::{IntervalSet, ParseError};
 use std::ops::RangeInclusive;
 
-fn parse_range(input: &str) -> std::ops::RangeInclusive<u32> {
-    let ranges: Vec<u32> = input
-        .split('-')
-        .map(|x| x.parse::<u32>().unwrap())
-        .collect();
-    assert!(ranges.len() == 2);
-    (ranges[0])..=(ranges[1])
+fn parse_range(input: &str) -> Result<RangeInclusive<u32>, ParseError> {
+    let (start, end) = pattern::<(u32, u32)>("{}-{}", input)?;
+    if start > end {
+        return Err(ParseError::at(input, input, "an ascending range"));
+    }
+    Ok(start..=end)
 }
 fn overlap_ranges(l: RangeInclusive<u32>, r: RangeInclusive<u32>) -> bool {
-    (l.contains(r.start()) && l.contains(r.end())) || (r.contains(l.start()) && r.contains(l.end()))
+    let (l, r) = (IntervalSet::from(l), IntervalSet::from(r));
+    l.is_subset(&r) || r.is_subset(&l)
 }
 
 fn overlap_ranges_bool(l: RangeInclusive<u32>, r: RangeInclusive<u32>) -> bool {
-    let l_set: HashSet<u32> = HashSet::from_iter(l);
-    let r_set: HashSet<u32> = HashSet::from_iter(r);
-    l_set.intersection(&r_set).count() != 0
+    !IntervalSet::from(l).is_disjoint(&IntervalSet::from(r))
+}
+
+#[cfg(test)]
+fn overlap_ranges_n(l: RangeInclusive<u32>, r: RangeInclusive<u32>) -> u128 {
+    IntervalSet::from(l).intersection(&r.into()).len()
 }
 
-#[allow(dead_code)]
-fn overlap_ranges_n(l: RangeInclusive<u32>, r: RangeInclusive<u32>) -> u32 {
-    let l_set: HashSet<u32> = HashSet::from_iter(l);
-    let r_set: HashSet<u32> = HashSet::from_iter(r);
-    u32::try_from(l_set.intersection(&r_set).count()).unwrap()
+type Pair = (RangeInclusive<u32>, RangeInclusive<u32>);
+
+pub fn parse(input: &str) -> Result<Vec<Pair>, ParseError> {
+    parse_lines(input, |line| {
+        let fields = fields("{},{}", line)?;
+        let range = |field| parse_range(field).map_err(|e| e.within(line, field));
+        Ok((range(fields[0])?, range(fields[1])?))
+    })
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(pairs: &[Pair]) -> Option<u32> {
     Some(
-        input
-            .lines()
-            .map(|l| l.split(','))
-            .map(|l| {
-                let ranges: Vec<&str> = l.collect();
-                assert!(ranges.len() == 2);
-                (parse_range(ranges[0]), parse_range(ranges[1]))
-            })
-            .map(|(l, r)| overlap_ranges(l, r))
+        pairs
+            .iter()
+            .map(|(l, r)| overlap_ranges(l.clone(), r.clone()))
             .map(u32::from)
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(pairs: &[Pair]) -> Option<u32> {
     Some(
-        input
-            .lines()
-            .map(|l| l.split(','))
-            .map(|l| {
-                let ranges: Vec<&str> = l.collect();
-                assert!(ranges.len() == 2);
-                (parse_range(ranges[0]), parse_range(ranges[1]))
-            })
-            // .filter(|(l, r)| overlap_ranges(l.clone(), r.clone()))
-            .map(|(l, r)| overlap_ranges_bool(l, r))
-            // .map(|x| u32::try_from(x).unwrap())
+        pairs
+            .iter()
+            .map(|(l, r)| overlap_ranges_bool(l.clone(), r.clone()))
             .map(u32::from)
             .sum(),
     )
@@ -63,8 +55,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 4);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve_parsed!(parse, part_one, part_two, input);
 }
 
 #[cfg(test)]
@@ -73,23 +64,40 @@ mod tests {
 
     #[test]
     fn test_parse_range() {
-        assert_eq!(parse_range("1-4"), 1..=4);
-        assert_eq!(parse_range("1-1"), 1..=1);
+        assert_eq!(parse_range("1-4"), Ok(1..=4));
+        assert_eq!(parse_range("1-1"), Ok(1..=1));
+        assert_eq!(
+            parse_range("5-2").unwrap_err().to_string(),
+            "input:1:1: expected an ascending range, found '5-2'"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("2-4,6-8\n"), Ok(vec![(2..=4, 6..=8)]));
+        assert_eq!(
+            parse("2-4,6-8\n2-3,4-x\n").unwrap_err().to_string(),
+            "input:2:7: expected `u32`, found 'x'"
+        );
+        assert_eq!(
+            parse("2-4,8-6\n").unwrap_err().to_string(),
+            "input:1:5: expected an ascending range, found '8-6'"
+        );
     }
 
     #[test]
     fn test_overlap_ranges() {
-        assert_eq!(overlap_ranges(1..=4, 2..=5), false);
-        assert_eq!(overlap_ranges(1..=4, 2..=4), true);
-        assert_eq!(overlap_ranges(2..=5, 1..=4), false);
-        assert_eq!(overlap_ranges(2..=4, 1..=4), true);
+        assert!(!overlap_ranges(1..=4, 2..=5));
+        assert!(overlap_ranges(1..=4, 2..=4));
+        assert!(!overlap_ranges(2..=5, 1..=4));
+        assert!(overlap_ranges(2..=4, 1..=4));
     }
 
     #[test]
     fn test_overlap_ranges_bool() {
-        assert_eq!(overlap_ranges_bool(1..=4, 2..=5), true);
-        assert_eq!(overlap_ranges_bool(1..=1, 1..=1), true);
-        assert_eq!(overlap_ranges_bool(1..=4, 5..=7), false);
+        assert!(overlap_ranges_bool(1..=4, 2..=5));
+        assert!(overlap_ranges_bool(1..=1, 1..=1));
+        assert!(!overlap_ranges_bool(1..=4, 5..=7));
     }
 
     #[test]
@@ -102,12 +110,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 4);
-        assert_eq!(part_one(&input), Some(2));
+        assert_eq!(part_one(&parse(&input).unwrap()), Some(2));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 4);
-        assert_eq!(part_two(&input), Some(4));
+        assert_eq!(part_two(&parse(&input).unwrap()), Some(4));
     }
 }
@@ -1,124 +1,163 @@
-use regex::Regex;
+use advent_of_code::helpers::parse::{blocks, fields, parse_field, parse_lines, unsigned};
+use advent_of_code::helpers::{Grid, ParseError};
 
-#[derive(Debug, PartialEq)]
-struct Supplies {
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Supplies {
     len: usize,
     stacks: Vec<Stack>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct Stack {
     crates: Vec<char>,
     index: usize,
 }
 
-#[derive(Debug, PartialEq)]
-struct Move {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Move {
     from: usize,
     to: usize,
     quantity: usize,
 }
 
-fn parse_crate_line(input: &str) -> Vec<Option<char>> {
-    let re = Regex::new(r"\[(\w{1})\]").unwrap();
-    input
-        .chars()
-        .collect::<Vec<char>>()
-        .chunks(4)
-        .map(|c| c.iter().collect::<String>())
-        .map(|c| {
-            re.captures(c.as_str())
-                .map(|caps| caps[1].chars().next().unwrap())
+fn crates_len(input: &str) -> Result<usize, ParseError> {
+    let numbers_line = input.lines().last().unwrap_or_default();
+    let len = unsigned::<usize>(numbers_line)
+        .map_err(|e| e.within(input, numbers_line))?
+        .len();
+    if len == 0 {
+        return Err(ParseError::at(input, numbers_line, "the stack numbers"));
+    }
+    Ok(len)
+}
+
+fn parse_crates(input: &str) -> Result<Supplies, ParseError> {
+    let len: usize = crates_len(input)?;
+    let drawing = Grid::parse_padded(input, ' ');
+    // crates are drawn in every fourth column, stacked on top of the stack numbers.
+    let stacks = drawing
+        .columns()
+        .skip(1)
+        .step_by(4)
+        .enumerate()
+        .map(|(index, column)| Stack {
+            index,
+            crates: column
+                .rev()
+                .skip(1)
+                .take_while(|c| c.is_ascii_alphabetic())
+                .copied()
+                .collect(),
         })
-        .collect()
+        .collect();
+
+    Ok(Supplies { len, stacks })
 }
 
-fn crates_len(input: &str) -> usize {
-    let crates_line: Vec<&str> = input.lines().last().unwrap().split_whitespace().collect();
-    crates_line.len()
+fn parse_moves(input: &str, len: usize) -> Result<Vec<Move>, ParseError> {
+    parse_lines(input, |line| {
+        let fields = fields("move {} from {} to {}", line)?;
+        // stacks are numbered from 1.
+        let stack = |field| match parse_field::<usize>(line, field)? {
+            number @ 1.. if number <= len => Ok(number - 1),
+            _ => Err(ParseError::at(
+                line,
+                field,
+                format!("a stack from 1 to {len}"),
+            )),
+        };
+        Ok(Move {
+            quantity: parse_field(line, fields[0])?,
+            from: stack(fields[1])?,
+            to: stack(fields[2])?,
+        })
+    })
 }
 
-fn parse_crates(input: &str) -> Supplies {
-    let len: usize = crates_len(input);
-    let mut stacks = Vec::<Stack>::with_capacity(len);
-    for i in 0..len {
-        stacks.push(Stack {
-            index: i,
-            crates: Vec::<char>::new(),
-        });
-    }
-    let re = Regex::new(r"\d").unwrap();
-    for line in input.lines() {
-        if re.is_match(line) {
-            break;
-        }
-        let crates = parse_crate_line(line);
-        for (i, c) in crates.iter().enumerate() {
-            if let Some(c) = c {
-                stacks[i].crates.insert(0, *c);
-            }
+/// Checks that no move in `input` takes more crates than its stack holds at that point. Both parts
+/// move the same number of crates, only their order differs, so the stack heights suffice.
+fn check_moves(input: &str, supplies: &Supplies, moves: &[Move]) -> Result<(), ParseError> {
+    let mut heights: Vec<usize> = supplies.stacks.iter().map(|s| s.crates.len()).collect();
+    for (line, m) in input.lines().zip(moves) {
+        if m.quantity > heights[m.from] {
+            return Err(ParseError::at(
+                input,
+                line,
+                format!(
+                    "a move of at most {} crates from stack {}",
+                    heights[m.from],
+                    m.from + 1
+                ),
+            ));
         }
+        heights[m.from] -= m.quantity;
+        heights[m.to] += m.quantity;
     }
-
-    Supplies { len, stacks }
-}
-
-fn parse_moves(input: &str) -> Vec<Move> {
-    let re = Regex::new(r"move (\d{1,2}) from (\d{1}) to (\d{1})").unwrap();
-    input
-        .lines()
-        .map(|line| {
-            let caps = re.captures(line).unwrap();
-            Move {
-                quantity: caps[1].parse::<usize>().unwrap(),
-                from: caps[2].parse::<usize>().unwrap() - 1,
-                to: caps[3].parse::<usize>().unwrap() - 1,
-            }
-        })
-        .collect()
+    Ok(())
 }
 
 impl Supplies {
     fn move_stack(&mut self, m: Move) {
         for _ in 1..=m.quantity {
-            let from_char: char = self.stacks[m.from].crates.pop().unwrap();
+            let from_char: char = self.stacks[m.from]
+                .crates
+                .pop()
+                .expect("checked by check_moves");
             self.stacks[m.to].crates.push(from_char);
         }
     }
     fn move_stack_part2(&mut self, m: Move) {
         let mut v: Vec<char> = Vec::with_capacity(m.quantity);
         for _ in 1..=m.quantity {
-            v.insert(0, self.stacks[m.from].crates.pop().unwrap());
+            v.insert(
+                0,
+                self.stacks[m.from]
+                    .crates
+                    .pop()
+                    .expect("checked by check_moves"),
+            );
         }
         self.stacks[m.to].crates.extend(v);
     }
 }
 
+/// The top crate of every stack, stacks that were emptied have none.
 fn get_top_crates(supplies: &Supplies) -> String {
     supplies
         .stacks
         .iter()
-        .map(|stack| stack.crates.last().unwrap())
+        .filter_map(|stack| stack.crates.last())
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let parsed_str = input.split("\n\n").collect::<Vec<&str>>();
-    let mut crates = parse_crates(parsed_str[0]);
-    let moves = parse_moves(parsed_str[1]);
+pub fn parse(input: &str) -> Result<(Supplies, Vec<Move>), ParseError> {
+    let mut parsed_str = blocks(input);
+    let (Some(drawing), Some(moves)) = (parsed_str.next(), parsed_str.next()) else {
+        return Err(ParseError::at(
+            input,
+            &input[input.len()..],
+            "a drawing and moves separated by a blank line",
+        ));
+    };
+    let supplies = parse_crates(drawing).map_err(|e| e.within(input, drawing))?;
+    let parsed_moves = parse_moves(moves, supplies.len).map_err(|e| e.within(input, moves))?;
+    check_moves(moves, &supplies, &parsed_moves).map_err(|e| e.within(input, moves))?;
+    Ok((supplies, parsed_moves))
+}
+
+pub fn part_one((supplies, moves): &(Supplies, Vec<Move>)) -> Option<String> {
+    let mut crates = supplies.clone();
     for m in moves {
-        crates.move_stack(m);
+        crates.move_stack(*m);
     }
     let answer = get_top_crates(&crates);
     Some(answer)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let parsed_str = input.split("\n\n").collect::<Vec<&str>>();
-    let mut crates = parse_crates(parsed_str[0]);
-    let moves = parse_moves(parsed_str[1]);
+pub fn part_two((supplies, moves): &(Supplies, Vec<Move>)) -> Option<String> {
+    let mut crates = supplies.clone();
     for m in moves {
-        crates.move_stack_part2(m);
+        crates.move_stack_part2(*m);
     }
     let answer = get_top_crates(&crates);
     Some(answer)
@@ -126,34 +165,36 @@ pub fn part_two(input: &str) -> Option<String> {
 
 fn main() {
     let input = &advent_of_code::read_file("inputs", 5);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve_parsed!(parse, part_one, part_two, input);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::helpers::cycle::{state_after, Cycle};
 
     #[test]
     fn test_parse_crate_line() {
-        let input_full = "[Z] [M] [P]";
-        let input_half = "[N] [C]    ";
-        let input_empty = "    [D]    ";
-        assert_eq!(
-            vec![Some('Z'), Some('M'), Some('P')],
-            parse_crate_line(input_full)
-        );
-        assert_eq!(
-            vec![Some('N'), Some('C'), None],
-            parse_crate_line(input_half)
-        );
-        assert_eq!(vec![None, Some('D'), None], parse_crate_line(input_empty));
+        // the crates of a drawing with a single line of them, `None` for a gap.
+        let line = |line: &str| -> Vec<Option<char>> {
+            parse_crates(&format!("{line}\n 1   2   3"))
+                .unwrap()
+                .stacks
+                .into_iter()
+                .map(|stack| stack.crates.first().copied())
+                .collect()
+        };
+        assert_eq!(vec![Some('Z'), Some('M'), Some('P')], line("[Z] [M] [P]"));
+        assert_eq!(vec![Some('N'), Some('C'), None], line("[N] [C]    "));
+        assert_eq!(vec![None, Some('D'), None], line("    [D]    "));
+        // editors may strip the trailing spaces.
+        assert_eq!(vec![None, Some('D'), None], line("    [D]"));
     }
 
     #[test]
     fn test_crates_len() {
         let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3";
-        assert_eq!(3, crates_len(input));
+        assert_eq!(Ok(3), crates_len(input));
     }
 
     #[test]
@@ -176,7 +217,7 @@ mod tests {
                 },
             ],
         };
-        assert_eq!(supplies, parse_crates(input));
+        assert_eq!(Ok(supplies), parse_crates(input));
     }
 
     #[test]
@@ -206,7 +247,13 @@ mod tests {
                     to: 1
                 },
             ],
-            parse_moves(input)
+            parse_moves(input, 3).unwrap()
+        );
+        assert_eq!(
+            parse_moves("move 1 from 2 to 1\nmove 3 from 1 to 4\n", 3)
+                .unwrap_err()
+                .to_string(),
+            "input:2:18: expected a stack from 1 to 3, found '4'"
         );
     }
 
@@ -322,17 +369,71 @@ mod tests {
             ],
         };
         assert_eq!(get_top_crates(&supplies), "CEF");
+
+        let mut emptied = supplies.clone();
+        emptied.move_stack(Move {
+            quantity: 2,
+            from: 1,
+            to: 0,
+        });
+        assert_eq!(get_top_crates(&emptied), "DF");
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::read_file("examples", 5);
+        let broken = input.replace("move 3 from 1 to 3", "move 3 from 1 to x");
+        assert_eq!(
+            parse(&broken).unwrap_err().to_string(),
+            "input:7:18: expected `usize`, found 'x'"
+        );
+        assert!(parse("move 1 from 2 to 1").is_err());
+
+        let too_many = input.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        assert_eq!(
+            parse(&too_many).unwrap_err().to_string(),
+            "input:7:1: expected a move of at most 3 crates from stack 1, found 'move 4 from 1 to 3'"
+        );
+    }
+
+    #[test]
+    fn test_replay_cycle() {
+        // every replay of the moves puts the same number of crates back on each stack.
+        let input = "[B]        \n[A] [C] [D]\n 1   2   3 \n\nmove 2 from 1 to 3\nmove 1 from 3 to 2\nmove 2 from 2 to 1\nmove 1 from 3 to 2\n";
+        let (supplies, moves) = parse(input).unwrap();
+        let replay = |supplies: &Supplies| {
+            let mut next = supplies.clone();
+            for m in &moves {
+                next.move_stack(*m);
+            }
+            next
+        };
+
+        let cycle = Cycle::find(supplies.clone(), replay);
+        assert_eq!((cycle.start, cycle.length), (0, 2));
+        assert_eq!(get_top_crates(cycle.state_at(cycle.length)), "BCD");
+
+        let mut replayed = supplies.clone();
+        for n in 0..50 {
+            assert_eq!(cycle.state_at(n), &replayed);
+            assert_eq!(state_after(supplies.clone(), replay, n), replayed);
+            replayed = replay(&replayed);
+        }
+        assert_eq!(
+            cycle.state_at(1_000_000_000),
+            cycle.state_at(1_000_000_000 % cycle.length)
+        );
     }
 
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 5);
-        assert_eq!(part_one(&input), Some("CMZ".to_string()));
+        assert_eq!(part_one(&parse(&input).unwrap()), Some("CMZ".to_string()));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 5);
-        assert_eq!(part_two(&input), Some("MCD".to_string()));
+        assert_eq!(part_two(&parse(&input).unwrap()), Some("MCD".to_string()));
     }
 }
@@ -0,0 +1,232 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::answers::{self, AnswerHashes, Verdict, ANSWERS_DIR};
+use advent_of_code::aoc_cli::get_profile_input_path;
+use advent_of_code::calendar::{FIRST_DAY, LAST_DAY};
+use advent_of_code::profile::{self, DEFAULT_PROFILE, PROFILE_ENV};
+use advent_of_code::{encryption, parse_answer, provenance, EXEC_TIME_ENV};
+use std::fs;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+
+enum Task {
+    Export,
+    Import(String),
+    Verify,
+}
+
+struct Args {
+    task: Task,
+    /// `None` selects every day.
+    day: Option<u8>,
+    /// Whose answers these are. Defaults to the account label, see [`provenance::account`].
+    name: String,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let name = args
+        .opt_value_from_fn("--name", profile::parse)?
+        .unwrap_or_else(provenance::account);
+
+    let task = match args.subcommand()?.as_deref() {
+        Some("export") => Task::Export,
+        Some("import") => Task::Import(args.free_from_str()?),
+        Some("verify") => Task::Verify,
+        _ => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "expected one of the commands export, import or verify".into(),
+            })
+        }
+    };
+
+    Ok(Args {
+        task,
+        day: args.opt_free_from_str()?,
+        name,
+    })
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
+/// An input set's answers to one day, along with the hash of the input they were computed on.
+struct Solved {
+    profile: String,
+    input_sha256: String,
+    answers: Vec<(u8, String)>,
+}
+
+/// Runs `day` on the input of `profile` and collects the answers it reports.
+fn solve(day: u8, profile: Option<&str>) -> Option<Solved> {
+    let name = profile.unwrap_or(DEFAULT_PROFILE);
+    let input_path = get_profile_input_path(day, profile);
+    let input = match fs::read(&input_path) {
+        Ok(input) if !input.is_empty() => input,
+        _ if Path::new(&format!("{input_path}.enc")).exists() => {
+            println!("Day {day:02} ({name}): skipped, the input is encrypted. Run `cargo inputs decrypt` first.");
+            return None;
+        }
+        _ => {
+            println!("Day {day:02} ({name}): skipped, the input \"{input_path}\" is missing.");
+            return None;
+        }
+    };
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "--quiet", "--release", "--bin", &format!("{day:02}")])
+        .stderr(Stdio::null())
+        .env(EXEC_TIME_ENV, "1");
+    match profile {
+        Some(profile) => cmd.env(PROFILE_ENV, profile),
+        None => cmd.env_remove(PROFILE_ENV),
+    };
+
+    let output = match cmd.output() {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).into_owned(),
+        Err(e) => fail(&format!("Failed to run day {day:02}: {e}")),
+    };
+
+    Some(Solved {
+        profile: name.into(),
+        input_sha256: provenance::sha256_hex(&input),
+        answers: (1..=2)
+            .filter_map(|part| parse_answer(&output, part).map(|answer| (part, answer)))
+            .collect(),
+    })
+}
+
+/// Solves every input set of the selected days, see [`profile::input_sets`].
+fn solve_all(day: Option<u8>) -> Vec<(u8, Solved)> {
+    let days = match day {
+        Some(day) => day..=day,
+        None => FIRST_DAY..=LAST_DAY,
+    };
+
+    days.flat_map(|day| {
+        profile::input_sets(day)
+            .into_iter()
+            .filter_map(move |profile| solve(day, profile.as_deref()))
+            .map(move |solved| (day, solved))
+    })
+    .collect()
+}
+
+fn export(args: &Args) {
+    if !profile::is_valid_name(&args.name) {
+        fail(&format!(
+            "\"{}\" is not a valid name for an answer file, pass one with --name.",
+            args.name
+        ));
+    }
+
+    let mut hashes = AnswerHashes::new(&args.name, &encryption::generate_secret());
+    for (day, solved) in solve_all(args.day) {
+        for (part, answer) in &solved.answers {
+            hashes.add(day, *part, &solved.profile, &solved.input_sha256, answer);
+        }
+    }
+
+    if hashes.entries.is_empty() {
+        fail("No answers found, solve some days first.");
+    }
+
+    let path = answers::get_path(&args.name);
+    let result = fs::create_dir_all(ANSWERS_DIR).and_then(|_| fs::write(&path, hashes.to_string()));
+    if let Err(e) = result {
+        fail(&format!("Failed to write \"{path}\": {e}"));
+    }
+
+    println!(
+        "🎄 Exported {} answer hashes to \"{path}\". Share this file with your teammates.",
+        hashes.entries.len()
+    );
+}
+
+fn import(file: &str) {
+    let hashes = match fs::read_to_string(file) {
+        Ok(content) => AnswerHashes::parse(&content),
+        Err(e) => fail(&format!("Failed to read \"{file}\": {e}")),
+    };
+
+    let hashes = match hashes {
+        Ok(hashes) if profile::is_valid_name(&hashes.name) => hashes,
+        Ok(hashes) => fail(&format!(
+            "\"{}\" is not a valid teammate name.",
+            hashes.name
+        )),
+        Err(e) => fail(&format!("Failed to parse \"{file}\": {e}.")),
+    };
+
+    let path = answers::get_path(&hashes.name);
+    let result = fs::create_dir_all(ANSWERS_DIR).and_then(|_| fs::write(&path, hashes.to_string()));
+    if let Err(e) = result {
+        fail(&format!("Failed to write \"{path}\": {e}"));
+    }
+
+    println!(
+        "🎄 Imported {} answer hashes of {} to \"{path}\".",
+        hashes.entries.len(),
+        hashes.name
+    );
+}
+
+fn verify(args: &Args) {
+    let teammates: Vec<AnswerHashes> = answers::read_all()
+        .into_iter()
+        .filter(|hashes| hashes.name != args.name)
+        .collect();
+
+    if teammates.is_empty() {
+        fail(&format!(
+            "No answers of teammates found in \"{ANSWERS_DIR}\", import some with `cargo answers import <file>`."
+        ));
+    }
+
+    let mut differs = false;
+    for (day, solved) in solve_all(args.day) {
+        for (part, answer) in &solved.answers {
+            let verdicts: Vec<String> = teammates
+                .iter()
+                .filter_map(|hashes| {
+                    match hashes.verify(day, *part, &solved.input_sha256, answer) {
+                        Verdict::Matches => Some(format!("matches teammate {}", hashes.name)),
+                        Verdict::Differs => {
+                            differs = true;
+                            Some(format!("differs from teammate {}", hashes.name))
+                        }
+                        Verdict::Unknown => None,
+                    }
+                })
+                .collect();
+
+            let verdicts = if verdicts.is_empty() {
+                "no teammate answer for this input".into()
+            } else {
+                verdicts.join(", ")
+            };
+            println!("Day {day:02} part {part} ({}): {verdicts}", solved.profile);
+        }
+    }
+
+    if differs {
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => fail(&format!("Failed to process arguments: {e}")),
+    };
+
+    match &args.task {
+        Task::Export => export(&args),
+        Task::Import(file) => import(file),
+        Task::Verify => verify(&args),
+    }
+}
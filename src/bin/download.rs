@@ -2,22 +2,215 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
-use std::process;
+use advent_of_code::aoc_cli::{self, AocCliError, Backend, Failure};
+use advent_of_code::calendar::{self, Clock, SystemClock};
+use advent_of_code::parse_duration;
+use advent_of_code::profile::{self, PROFILE_ENV};
+use std::env;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::process::{self, Command};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Pause between two requests when downloading several days.
+const DEFAULT_DELAY: Duration = Duration::from_secs(5);
+
+/// Attempts to download a day that was just unlocked, in case the local clock runs ahead.
+const UNLOCK_ATTEMPTS: u32 = 5;
+const UNLOCK_RETRY_DELAY: Duration = Duration::from_secs(2);
 
 struct Args {
-    day: u8,
+    /// `None` selects today's puzzle.
+    days: Option<RangeInclusive<u8>>,
     year: Option<u16>,
+    delay: Duration,
+    wait: bool,
+    scaffold: bool,
+    force: bool,
+    profile: Option<String>,
+}
+
+/// Parses a single day (`7`) or an inclusive range of days (`1-10` or `1..10`).
+fn parse_days(val: &str) -> Result<RangeInclusive<u8>, String> {
+    let (start, end) = val
+        .split_once("..")
+        .or_else(|| val.split_once('-'))
+        .unwrap_or((val, val));
+
+    let parse_day = |day: &str| -> Result<u8, String> {
+        match day.trim().parse::<u8>() {
+            Ok(day) if (calendar::FIRST_DAY..=calendar::LAST_DAY).contains(&day) => Ok(day),
+            _ => Err(format!(
+                "\"{day}\" is not a day between {} and {}",
+                calendar::FIRST_DAY,
+                calendar::LAST_DAY
+            )),
+        }
+    };
+
+    let days = parse_day(start)?..=parse_day(end)?;
+    if days.is_empty() {
+        return Err(format!("\"{val}\" is an empty range of days"));
+    }
+    Ok(days)
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
+    let all = args.contains("--all");
+    let wait = args.contains("--wait");
+    let scaffold = args.contains("--scaffold");
+    let force = args.contains("--force");
+    let year = args.opt_value_from_str(["-y", "--year"])?;
+    let profile = args.opt_value_from_fn("--profile", profile::parse)?;
+    let delay = args
+        .opt_value_from_fn("--delay", parse_duration)?
+        .unwrap_or(DEFAULT_DELAY);
+
+    let days = if all {
+        Some(calendar::FIRST_DAY..=calendar::LAST_DAY)
+    } else {
+        args.opt_free_from_fn(parse_days)?
+    };
+
     Ok(Args {
-        day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
+        days,
+        year,
+        delay,
+        wait,
+        scaffold,
+        force,
+        profile,
     })
 }
 
+fn exit_with_error(e: &AocCliError) -> ! {
+    eprintln!("{e}");
+    if let Some(hint) = e.hint() {
+        eprintln!("hint: {hint}");
+    }
+    process::exit(1);
+}
+
+#[derive(Default)]
+struct Summary {
+    fetched: Vec<u8>,
+    skipped: Vec<u8>,
+    locked: Vec<u8>,
+    failed: Vec<u8>,
+}
+
+fn format_days(days: &[u8]) -> String {
+    if days.is_empty() {
+        "-".into()
+    } else {
+        days.iter()
+            .map(|day| format!("{day:02}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Downloads every unlocked day in `days` that is not present yet. Days that are present
+/// are skipped, so an interrupted run continues where it stopped when started again.
+fn download_all(backend: &Backend, days: RangeInclusive<u8>, args: &Args) -> Summary {
+    let year = args
+        .year
+        .unwrap_or_else(|| calendar::current_event_year(SystemTime::now()));
+
+    let mut summary = Summary::default();
+    let mut requested = false;
+
+    for day in days {
+        if aoc_cli::is_downloaded(day) {
+            summary.skipped.push(day);
+            continue;
+        }
+
+        if !calendar::is_unlocked(year, day, SystemTime::now()) {
+            summary.locked.push(day);
+            continue;
+        }
+
+        if requested {
+            thread::sleep(args.delay);
+        }
+        requested = true;
+
+        println!("🎄 Downloading day {day:02} of {year}...");
+        match aoc_cli::download(backend, day, Some(year), args.force) {
+            Ok(_) => summary.fetched.push(day),
+            Err(e) if e.failure() == Some(Failure::NotUnlocked) => summary.locked.push(day),
+            Err(e) => {
+                eprintln!("{e}");
+                if let Some(hint) = e.hint() {
+                    eprintln!("hint: {hint}");
+                }
+                summary.failed.push(day);
+
+                if e.failure() == Some(Failure::RateLimited) {
+                    eprintln!("Stopping early, run the command again later to resume.");
+                    break;
+                }
+            }
+        }
+    }
+
+    summary
+}
+
+/// Waits for `day` to unlock while showing a countdown, then downloads it. Returns the year it
+/// was downloaded for.
+fn wait_and_download(clock: &impl Clock, backend: &Backend, day: u8, args: &Args) -> u16 {
+    let year = args
+        .year
+        .unwrap_or_else(|| calendar::current_event_year(clock.now()));
+
+    // an expired session cookie is better noticed now than at unlock time.
+    if let Err(e) = aoc_cli::check_session(backend) {
+        exit_with_error(&e);
+    }
+
+    calendar::wait_for_unlock(clock, year, day, |remaining| {
+        print!(
+            "\r⏳ Day {day:02} of {year} unlocks in {}",
+            calendar::format_countdown(remaining)
+        );
+        io::stdout().flush().ok();
+    });
+    println!("\r🎄 Day {day:02} of {year} is unlocked!                  ");
+
+    for attempt in 1..=UNLOCK_ATTEMPTS {
+        match aoc_cli::download(backend, day, Some(year), args.force) {
+            Ok(_) => return year,
+            Err(e)
+                if matches!(e.failure(), Some(Failure::NotUnlocked | Failure::NotFound))
+                    && attempt < UNLOCK_ATTEMPTS =>
+            {
+                println!("Not available yet, retrying in {UNLOCK_RETRY_DELAY:?}...");
+                clock.sleep(UNLOCK_RETRY_DELAY);
+            }
+            Err(e) => exit_with_error(&e),
+        }
+    }
+    unreachable!("the last attempt either downloads or exits")
+}
+
+fn scaffold(day: u8, year: Option<u16>) {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["scaffold", &day.to_string()]);
+    if let Some(year) = year {
+        cmd.args(["--year", &year.to_string()]);
+    }
+    let status = cmd.status();
+
+    if !status.is_ok_and(|status| status.success()) {
+        eprintln!("Failed to scaffold day {day:02}.");
+        process::exit(1);
+    }
+}
+
 fn main() {
     let args = match parse_args() {
         Ok(args) => args,
@@ -27,20 +220,75 @@ fn main() {
         }
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    // paths and the session file are resolved from the environment, see `profile::current`.
+    if let Some(profile) = &args.profile {
+        env::set_var(PROFILE_ENV, profile);
     }
 
-    match aoc_cli::download(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
+    let backend = match Backend::detect() {
+        Ok(backend) => backend,
+        Err(e) => exit_with_error(&e),
+    };
+
+    let days = match &args.days {
+        Some(days) if days.start() != days.end() => days.clone(),
+        single => {
+            let day = match calendar::day_or_today(
+                single.as_ref().map(|d| *d.start()),
+                SystemTime::now(),
+            ) {
+                Ok(day) => day,
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            };
+            let year = if args.wait {
+                Some(wait_and_download(&SystemClock, &backend, day, &args))
+            } else if let Err(e) = aoc_cli::download(&backend, day, args.year, args.force) {
+                exit_with_error(&e);
+            } else {
+                args.year
+            };
+            if args.scaffold {
+                scaffold(day, year);
             }
+            return;
         }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {e}");
-            process::exit(1);
-        }
+    };
+
+    if args.wait || args.scaffold {
+        eprintln!("--wait and --scaffold can only be used with a single day.");
+        process::exit(1);
+    }
+
+    let summary = download_all(&backend, days, &args);
+
+    println!("---");
+    println!("🎄 Fetched: {}", format_days(&summary.fetched));
+    println!(
+        "🎄 Skipped (already present): {}",
+        format_days(&summary.skipped)
+    );
+    println!("🎄 Still locked: {}", format_days(&summary.locked));
+    if !summary.failed.is_empty() {
+        println!("🎄 Failed: {}", format_days(&summary.failed));
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("7"), Ok(7..=7));
+        assert_eq!(parse_days("1-10"), Ok(1..=10));
+        assert_eq!(parse_days("3..5"), Ok(3..=5));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("20-26").is_err());
+        assert!(parse_days("10-1").is_err());
+        assert!(parse_days("one").is_err());
     }
 }
@@ -0,0 +1,155 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::aoc_cli::get_input_path;
+use advent_of_code::calendar::{FIRST_DAY, LAST_DAY};
+use advent_of_code::encryption::{self, InputKey, KEY_ENV, KEY_FILE};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process;
+
+enum Command {
+    Keygen,
+    Encrypt,
+    Decrypt,
+}
+
+struct Args {
+    command: Command,
+    /// `None` selects every day.
+    day: Option<u8>,
+    force: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let force = args.contains("--force");
+    let command = match args.subcommand()?.as_deref() {
+        Some("keygen") => Command::Keygen,
+        Some("encrypt") => Command::Encrypt,
+        Some("decrypt") => Command::Decrypt,
+        _ => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "expected one of the commands keygen, encrypt or decrypt".into(),
+            })
+        }
+    };
+    Ok(Args {
+        command,
+        day: args.opt_free_from_str()?,
+        force,
+    })
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
+fn has_content(path: &str) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+fn keygen() {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    // like the session file, the key is readable by the current user only.
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = match options.open(KEY_FILE) {
+        Ok(file) => file,
+        Err(e) => fail(&format!("Failed to create key file \"{KEY_FILE}\": {e}")),
+    };
+
+    if let Err(e) = file.write_all(encryption::generate_secret().as_bytes()) {
+        fail(&format!("Failed to write key file: {e}"));
+    }
+
+    println!("🎄 Wrote a new key to \"{KEY_FILE}\". Keep it out of git and share it with your team, e.g. as the {KEY_ENV} secret in CI.");
+}
+
+fn encrypt(key: &InputKey, day: u8) -> Option<String> {
+    let input_path = get_input_path(day);
+    if !has_content(&input_path) {
+        return None;
+    }
+
+    let encrypted_path = encryption::get_encrypted_path(day);
+    let result = fs::read(&input_path)
+        .and_then(|input| fs::write(&encrypted_path, encryption::encrypt(key, &input)));
+
+    match result {
+        Ok(_) => Some(format!(
+            "Encrypted \"{input_path}\" to \"{encrypted_path}\"."
+        )),
+        Err(e) => fail(&format!("Failed to encrypt \"{input_path}\": {e}")),
+    }
+}
+
+fn decrypt(key: &InputKey, day: u8, force: bool) -> Option<String> {
+    let encrypted_path = encryption::get_encrypted_path(day);
+    let input_path = get_input_path(day);
+    if !Path::new(&encrypted_path).exists() {
+        return None;
+    }
+
+    if has_content(&input_path) && !force {
+        return Some(format!(
+            "Skipped \"{input_path}\", it already exists. Pass --force to overwrite it."
+        ));
+    }
+
+    let input = fs::read(&encrypted_path)
+        .map_err(encryption::EncryptionError::from)
+        .and_then(|data| encryption::decrypt(key, &data));
+
+    match input.map(|input| fs::write(&input_path, input)) {
+        Ok(Ok(_)) => Some(format!(
+            "Decrypted \"{encrypted_path}\" to \"{input_path}\"."
+        )),
+        Ok(Err(e)) => fail(&format!("Failed to write \"{input_path}\": {e}")),
+        Err(e) => fail(&format!("Failed to decrypt \"{encrypted_path}\": {e}")),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => fail(&format!("Failed to process arguments: {e}")),
+    };
+
+    if let Command::Keygen = args.command {
+        keygen();
+        return;
+    }
+
+    let key = match InputKey::load() {
+        Ok(key) => key,
+        Err(e) => fail(&e.to_string()),
+    };
+
+    let days = match args.day {
+        Some(day) => day..=day,
+        None => FIRST_DAY..=LAST_DAY,
+    };
+
+    let mut processed = 0;
+    for day in days {
+        let message = match args.command {
+            Command::Encrypt => encrypt(&key, day),
+            Command::Decrypt => decrypt(&key, day, args.force),
+            Command::Keygen => unreachable!(),
+        };
+        if let Some(message) = message {
+            println!("{message}");
+            processed += 1;
+        }
+    }
+
+    if processed == 0 {
+        println!("No inputs found.");
+    }
+}
@@ -0,0 +1,210 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::aoc_cli::AocCliError;
+use advent_of_code::calendar::{self, FIRST_DAY, LAST_DAY};
+use advent_of_code::leaderboard::{self, Leaderboard, Member, LEADERBOARD_ID_ENV};
+use advent_of_code::site::Client;
+use std::time::{Duration, SystemTime};
+use std::{env, process};
+
+struct Args {
+    id: Option<u64>,
+    year: Option<u16>,
+    /// show the completion times of a single day instead.
+    day: Option<u8>,
+    /// only use the cached leaderboard.
+    offline: bool,
+    /// fetch even if the cached leaderboard is recent.
+    refresh: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        id: args.opt_value_from_str("--id")?,
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        day: args.opt_value_from_str("--day")?,
+        offline: args.contains("--offline"),
+        refresh: args.contains("--refresh"),
+    })
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{msg}");
+    process::exit(1);
+}
+
+fn exit_with_error(e: &AocCliError) -> ! {
+    eprintln!("{e}");
+    if let Some(hint) = e.hint() {
+        eprintln!("hint: {hint}");
+    }
+    process::exit(1);
+}
+
+/// Loads the leaderboard from the cache while it is recent, otherwise from the site.
+fn load(args: &Args, year: u16, id: u64) -> String {
+    let cached = leaderboard::read_cache(year, id);
+
+    if let Some((json, fetched_at)) = &cached {
+        let age = fetched_at.elapsed().unwrap_or_default();
+        if args.offline || (!args.refresh && age < leaderboard::MIN_REFRESH_INTERVAL) {
+            return json.clone();
+        }
+    } else if args.offline {
+        fail(&format!(
+            "No cached leaderboard at \"{}\", run without --offline first.",
+            leaderboard::get_cache_path(year, id)
+        ));
+    }
+
+    let fetched = Client::from_env().and_then(|client| leaderboard::fetch(&client, year, id));
+    match (fetched, cached) {
+        (Ok(json), _) => {
+            if let Err(e) = leaderboard::write_cache(year, id, &json, SystemTime::now()) {
+                eprintln!("Failed to cache the leaderboard: {e}");
+            }
+            json
+        }
+        (Err(e), Some((json, fetched_at))) => {
+            eprintln!("{e}");
+            eprintln!(
+                "Showing the leaderboard cached at {}.",
+                calendar::format_utc(fetched_at)
+            );
+            json
+        }
+        (Err(e), None) => exit_with_error(&e.into()),
+    }
+}
+
+fn format_stars(member: &Member, days: u8) -> String {
+    (FIRST_DAY..=days)
+        .map(|day| match member.stars_on(day) {
+            2 => '★',
+            1 => '☆',
+            _ => '·',
+        })
+        .collect()
+}
+
+fn format_delta(member: &Member, previous: Option<&Leaderboard>) -> String {
+    let Some(before) = previous.and_then(|previous| previous.member(member.id)) else {
+        return String::new();
+    };
+    match i64::from(member.local_score) - i64::from(before.local_score) {
+        0 => String::new(),
+        delta => format!("{delta:+}"),
+    }
+}
+
+fn print_ranking(leaderboard: &Leaderboard, previous: Option<&Leaderboard>, days: u8) {
+    let header: String = (FIRST_DAY..=days)
+        .map(|day| (day % 10).to_string())
+        .collect();
+    println!("{:>4}  {:>5}  {:>5}  {header}", "", "score", "Δ");
+
+    for (rank, member) in leaderboard.ranked().into_iter().enumerate() {
+        println!(
+            "{:>4}  {:>5}  {:>5}  {}  {}",
+            format!("{})", rank + 1),
+            member.local_score,
+            format_delta(member, previous),
+            format_stars(member, days),
+            member.display_name(),
+        );
+    }
+}
+
+fn format_solve_time(time: Option<Duration>) -> String {
+    time.map_or_else(|| "-".into(), calendar::format_countdown)
+}
+
+/// Prints how long after unlocking each member took for both parts of `day`.
+fn print_day(leaderboard: &Leaderboard, year: u16, day: u8) {
+    let mut members: Vec<&Member> = leaderboard
+        .ranked()
+        .into_iter()
+        .filter(|member| member.stars_on(day) > 0)
+        .collect();
+    // members who finished both parts first, then by how long they took.
+    members.sort_by_key(|member| {
+        (
+            member.solve_time(year, day, 2).is_none(),
+            member.solve_time(year, day, 2),
+            member.solve_time(year, day, 1),
+        )
+    });
+
+    if members.is_empty() {
+        println!("Nobody has solved day {day:02} yet.");
+        return;
+    }
+
+    println!("{:>4}  {:>12}  {:>12}", "", "part 1", "part 2");
+    for (rank, member) in members.into_iter().enumerate() {
+        println!(
+            "{:>4}  {:>12}  {:>12}  {}",
+            format!("{})", rank + 1),
+            format_solve_time(member.solve_time(year, day, 1)),
+            format_solve_time(member.solve_time(year, day, 2)),
+            member.display_name(),
+        );
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    // the id is part of the cache path, so only numbers are accepted.
+    let id = match env::var(LEADERBOARD_ID_ENV) {
+        Ok(id) if args.id.is_none() => match id.trim().parse::<u64>() {
+            Ok(id) => Some(id),
+            Err(_) => fail(&format!(
+                "{LEADERBOARD_ID_ENV} \"{id}\" is not a leaderboard id, expected a number."
+            )),
+        },
+        _ => args.id,
+    };
+    let Some(id) = id else {
+        eprintln!("No leaderboard id given.");
+        eprintln!("hint: Pass --id or set {LEADERBOARD_ID_ENV}, the id is the last segment of the leaderboard's url.");
+        process::exit(1);
+    };
+
+    let now = SystemTime::now();
+    let year = args
+        .year
+        .unwrap_or_else(|| calendar::current_event_year(now));
+
+    let json = load(&args, year, id);
+    let leaderboard = match Leaderboard::parse(&json) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => fail(&format!("Failed to parse the leaderboard: {e}")),
+    };
+
+    if let Some(day) = args.day {
+        if !(FIRST_DAY..=LAST_DAY).contains(&day) {
+            fail(&format!("Day {day} is not part of the event."));
+        }
+        print_day(&leaderboard, year, day);
+        return;
+    }
+
+    let days = (FIRST_DAY..=LAST_DAY)
+        .rfind(|&day| calendar::is_unlocked(year, day, now))
+        .unwrap_or(FIRST_DAY);
+    print_ranking(
+        &leaderboard,
+        leaderboard::read_previous(year, id).as_ref(),
+        days,
+    );
+}
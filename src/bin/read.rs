@@ -2,18 +2,19 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
+use advent_of_code::{aoc_cli, calendar};
 use std::process;
+use std::time::SystemTime;
 
 struct Args {
-    day: u8,
+    day: Option<u8>,
     year: Option<u16>,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
-        day: args.free_from_str()?,
+        day: args.opt_free_from_str()?,
         year: args.opt_value_from_str(["-y", "--year"])?,
     })
 }
@@ -27,20 +28,22 @@ fn main() {
         }
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    match aoc_cli::read(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
+    let day = match calendar::day_or_today(args.day, SystemTime::now()) {
+        Ok(day) => day,
         Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {e}");
+            eprintln!("{e}");
             process::exit(1);
         }
+    };
+
+    let result =
+        aoc_cli::Backend::detect().and_then(|backend| aoc_cli::read(&backend, day, args.year));
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        if let Some(hint) = e.hint() {
+            eprintln!("hint: {hint}");
+        }
+        process::exit(1);
     }
 }
@@ -2,10 +2,13 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use advent_of_code::calendar;
+use advent_of_code::stats::{self, Event};
 use std::{
     fs::{File, OpenOptions},
     io::Write,
     process,
+    time::SystemTime,
 };
 
 const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<u32> {
@@ -29,20 +32,29 @@ mod tests {
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
+        advent_of_code::stats::assert_example(DAY, 1, part_one(&input), None);
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
+        advent_of_code::stats::assert_example(DAY, 2, part_two(&input), None);
     }
 }
 "###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
+struct Args {
+    day: Option<u8>,
+    /// The event the solve times are recorded for, see [`stats::record`].
+    year: Option<u16>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        day: args.opt_free_from_str()?,
+    })
 }
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
@@ -50,14 +62,26 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
 fn main() {
-    let day = match parse_args() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    let day = match calendar::day_or_today(args.day, SystemTime::now()) {
         Ok(day) => day,
-        Err(_) => {
-            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
+        Err(e) => {
+            eprintln!("{e} example: `cargo scaffold 7`");
             process::exit(1);
         }
     };
@@ -106,6 +130,13 @@ fn main() {
         }
     }
 
+    let year = args
+        .year
+        .unwrap_or_else(|| calendar::current_event_year(SystemTime::now()));
+    if let Err(e) = stats::record(year, day, Event::Scaffolded) {
+        eprintln!("Failed to record the scaffolding time: {e}");
+    }
+
     println!("---");
     println!(
         "🎄 Type `cargo solve {}` to run your solution.",
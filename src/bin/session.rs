@@ -0,0 +1,149 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::aoc_cli::AocCliError;
+use advent_of_code::profile::{self, PROFILE_ENV};
+use advent_of_code::site::{self, Client, SESSION_ENV};
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process;
+
+enum Task {
+    /// `None` reads the cookie from stdin, so it does not end up in the shell history.
+    Set(Option<String>),
+    Check,
+    ShowPath,
+}
+
+struct Args {
+    task: Task,
+    /// skip validating the cookie before saving it.
+    no_check: bool,
+    profile: Option<String>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let no_check = args.contains("--no-check");
+    let profile = args.opt_value_from_fn("--profile", profile::parse)?;
+
+    let task = match args.subcommand()?.as_deref() {
+        Some("set") => Task::Set(args.opt_free_from_str()?),
+        Some("check") => Task::Check,
+        Some("show-path") => Task::ShowPath,
+        _ => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "expected one of the commands set, check or show-path".into(),
+            })
+        }
+    };
+
+    Ok(Args {
+        task,
+        no_check,
+        profile,
+    })
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
+fn exit_with_error(e: &AocCliError) -> ! {
+    eprintln!("{e}");
+    if let Some(hint) = e.hint() {
+        eprintln!("hint: {hint}");
+    }
+    process::exit(1);
+}
+
+fn session_path() -> PathBuf {
+    match site::session_path() {
+        Some(path) => path,
+        None => fail("Could not determine the home directory."),
+    }
+}
+
+fn read_session() -> String {
+    print!("Paste the session cookie: ");
+    io::stdout().flush().ok();
+
+    let mut line = String::new();
+    if let Err(e) = io::stdin().lock().read_line(&mut line) {
+        fail(&format!("Failed to read the session cookie: {e}"));
+    }
+    line
+}
+
+fn set(session: Option<String>, no_check: bool) {
+    let session = match site::parse_session(&session.unwrap_or_else(read_session)) {
+        Ok(session) => session,
+        Err(e) => fail(&format!("Invalid session cookie: {e}.")),
+    };
+
+    if !no_check {
+        println!(
+            "🎄 Checking the session cookie with {}...",
+            site::site_url()
+        );
+        if let Err(e) = Client::new(&site::site_url(), &session).check_session() {
+            exit_with_error(&e.into());
+        }
+    }
+
+    let path = session_path();
+    if let Err(e) = site::save_session(&path, &session) {
+        fail(&format!("Failed to write \"{}\": {e}", path.display()));
+    }
+    println!("🎄 Saved the session cookie to \"{}\".", path.display());
+
+    if site::session_override().is_some() {
+        println!("note: {SESSION_ENV} is set and takes precedence over the saved cookie.");
+    }
+}
+
+fn check() {
+    let source = match site::session_override() {
+        Some(_) => SESSION_ENV.to_string(),
+        None => format!("\"{}\"", session_path().display()),
+    };
+    println!(
+        "🎄 Checking the session cookie from {source} with {}...",
+        site::site_url()
+    );
+
+    if let Err(e) = Client::from_env().and_then(|client| client.check_session()) {
+        exit_with_error(&e.into());
+    }
+    println!("🎄 The session cookie is valid.");
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            eprintln!("usage: `cargo session set [<cookie>]`, `cargo session check` or `cargo session show-path`");
+            process::exit(1);
+        }
+    };
+
+    // the session file is resolved from the environment, see `profile::current`.
+    if let Some(profile) = &args.profile {
+        env::set_var(PROFILE_ENV, profile);
+    }
+
+    match args.task {
+        Task::Set(session) => set(session, args.no_check),
+        Task::Check => check(),
+        Task::ShowPath => {
+            println!("{}", session_path().display());
+            if site::session_override().is_some() {
+                eprintln!("note: {SESSION_ENV} is set and takes precedence over this file.");
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::calendar;
+use advent_of_code::profile::{self, PROFILE_ENV};
+use std::process::{self, Command};
+use std::time::SystemTime;
+
+struct Args {
+    day: Option<u8>,
+    release: bool,
+    profile: Option<String>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        release: args.contains("--release"),
+        profile: args.opt_value_from_fn("--profile", profile::parse)?,
+        day: args.opt_free_from_str()?,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    let day = match calendar::day_or_today(args.day, SystemTime::now()) {
+        Ok(day) => day,
+        Err(e) => {
+            eprintln!("{e} example: `cargo solve 7`");
+            process::exit(1);
+        }
+    };
+
+    let day_padded = format!("{day:02}");
+    let mut cmd_args = vec!["run", "--bin", &day_padded];
+    if args.release {
+        cmd_args.push("--release");
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args);
+    if let Some(profile) = &args.profile {
+        cmd.env(PROFILE_ENV, profile);
+    }
+
+    match cmd.status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to run day {day_padded}: {e}");
+            process::exit(1);
+        }
+    }
+}
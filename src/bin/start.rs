@@ -0,0 +1,108 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{aoc_cli, calendar, puzzle};
+use std::fs;
+use std::path::Path;
+use std::process::{self, Command};
+use std::time::SystemTime;
+
+struct Args {
+    day: Option<u8>,
+    year: Option<u16>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        day: args.opt_free_from_str()?,
+    })
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
+fn scaffold(day: u8, year: Option<u16>, day_padded: &str) {
+    let module_path = format!("src/bin/{day_padded}.rs");
+    if Path::new(&module_path).exists() {
+        println!("Module \"{module_path}\" already exists, skipping scaffold.");
+        return;
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["scaffold", &day.to_string()]);
+    if let Some(year) = year {
+        cmd.args(["--year", &year.to_string()]);
+    }
+    let status = cmd.status();
+
+    if !status.is_ok_and(|status| status.success()) {
+        fail(&format!("Failed to scaffold day {day_padded}."));
+    }
+}
+
+fn download(day: u8, year: Option<u16>) {
+    if aoc_cli::is_downloaded(day) {
+        println!("Input and puzzle for day {day:02} already exist, skipping download.");
+        return;
+    }
+
+    if let Err(e) =
+        aoc_cli::Backend::detect().and_then(|backend| aoc_cli::download(&backend, day, year, false))
+    {
+        eprintln!("{e}");
+        if let Some(hint) = e.hint() {
+            eprintln!("hint: {hint}");
+        }
+        process::exit(1);
+    }
+}
+
+fn extract_example(puzzle: &str, day_padded: &str) {
+    let example_path = format!("src/examples/{day_padded}.txt");
+    if fs::metadata(&example_path).is_ok_and(|m| m.len() > 0) {
+        println!("Example file \"{example_path}\" is not empty, skipping extraction.");
+        return;
+    }
+
+    match puzzle::example(puzzle) {
+        Some(example) => match fs::write(&example_path, example) {
+            Ok(_) => println!("Wrote example to \"{example_path}\"."),
+            Err(e) => fail(&format!("Failed to write example file: {e}")),
+        },
+        None => {
+            println!("Could not find an example in the puzzle, fill in \"{example_path}\" by hand.")
+        }
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => fail(&format!("Failed to process arguments: {e}")),
+    };
+
+    let day = match calendar::day_or_today(args.day, SystemTime::now()) {
+        Ok(day) => day,
+        Err(e) => fail(&format!("{e} example: `cargo start 7`")),
+    };
+    let day_padded = format!("{day:02}");
+
+    scaffold(day, args.year, &day_padded);
+    download(day, args.year);
+
+    let puzzle = match fs::read_to_string(aoc_cli::get_puzzle_path(day)) {
+        Ok(puzzle) => puzzle,
+        Err(e) => fail(&format!("Failed to read puzzle description: {e}")),
+    };
+
+    extract_example(&puzzle, &day_padded);
+
+    println!("---");
+    println!("🎄 {}", puzzle::PuzzleInfo::parse(day, &puzzle).heading());
+    println!("🎄 Type `cargo test --bin {day_padded}` to run your solution against the example.");
+}
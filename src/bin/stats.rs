@@ -0,0 +1,151 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::calendar::{self, FIRST_DAY, LAST_DAY};
+use advent_of_code::stats::{self, Timings};
+use advent_of_code::{parse_exec_time, puzzle, ANSI_BOLD, ANSI_RESET, EXEC_TIME_ENV};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
+
+/// Width of the longest bar in the trend chart.
+const BAR_WIDTH: u32 = 40;
+
+struct Args {
+    /// skip running the solutions to measure their runtime.
+    no_run: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        no_run: args.contains("--no-run"),
+    })
+}
+
+/// Runs `day` and returns the runtime it reports, like the runner does.
+fn runtime(day: u8) -> Option<Duration> {
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &format!("{day:02}")])
+        .stderr(Stdio::null())
+        .env(EXEC_TIME_ENV, "1")
+        .output()
+        .ok()?;
+    let elapsed = parse_exec_time(&String::from_utf8_lossy(&output.stdout));
+    (!elapsed.is_zero()).then_some(elapsed)
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".into(), calendar::format_countdown)
+}
+
+fn print_table(days: &[(Timings, Option<Duration>)]) {
+    let headings: Vec<String> = days
+        .iter()
+        .map(|(timings, _)| puzzle::heading(timings.day))
+        .collect();
+    let width = headings
+        .iter()
+        .map(|heading| heading.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "{:<width$}  {:>11}  {:>11}  {:>11}  {:>11}  {:>11}  {:>11}  {:>10}",
+        "", "started", "example 1", "part 1", "example 2", "part 2", "total", "runtime"
+    );
+    for ((timings, runtime), heading) in days.iter().zip(&headings) {
+        println!(
+            "{heading:<width$}  {:>11}  {:>11}  {:>11}  {:>11}  {:>11}  {:>11}  {:>10}",
+            format!("+{}", calendar::format_countdown(timings.start_delay())),
+            format_duration(timings.time_to_example(1)),
+            format_duration(timings.time_to_solve(1)),
+            format_duration(timings.time_to_example(2)),
+            format_duration(timings.time_to_solve(2)),
+            format_duration(timings.total()),
+            runtime.map_or_else(|| "-".into(), |runtime| format!("{runtime:.2?}")),
+        );
+    }
+}
+
+/// Prints the median, fastest and slowest day of `part`.
+fn print_part_trend(days: &[(Timings, Option<Duration>)], part: u8) {
+    let mut times: Vec<(Duration, u8)> = days
+        .iter()
+        .filter_map(|(timings, _)| Some((timings.time_to_solve(part)?, timings.day)))
+        .collect();
+    times.sort();
+
+    let (Some(fastest), Some(slowest)) = (times.first(), times.last()) else {
+        println!("part {part}: not solved yet.");
+        return;
+    };
+    println!(
+        "part {part}: median {}, fastest day {:02} ({}), slowest day {:02} ({})",
+        calendar::format_countdown(times[times.len() / 2].0),
+        fastest.1,
+        calendar::format_countdown(fastest.0),
+        slowest.1,
+        calendar::format_countdown(slowest.0),
+    );
+}
+
+/// Charts the total time of each solved day, to show how the event went.
+fn print_totals(days: &[(Timings, Option<Duration>)]) {
+    let totals: Vec<(u8, Duration)> = days
+        .iter()
+        .filter_map(|(timings, _)| Some((timings.day, timings.total()?)))
+        .collect();
+    let Some(longest) = totals.iter().map(|(_, total)| *total).max() else {
+        return;
+    };
+
+    println!("total per day:");
+    for (day, total) in totals {
+        let width = (total.as_secs_f64() / longest.as_secs_f64().max(1.0) * f64::from(BAR_WIDTH))
+            .ceil() as usize;
+        println!(
+            "Day {day:02} {:<bar$} {}",
+            "█".repeat(width),
+            calendar::format_countdown(total),
+            bar = BAR_WIDTH as usize,
+        );
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    let days: Vec<(Timings, Option<Duration>)> = (FIRST_DAY..=LAST_DAY)
+        .filter_map(stats::read)
+        .map(|timings| {
+            let runtime = if args.no_run {
+                None
+            } else {
+                runtime(timings.day)
+            };
+            (timings, runtime)
+        })
+        .collect();
+
+    if days.is_empty() {
+        println!(
+            "No solve times recorded yet, they are recorded from `cargo scaffold` on in \"{}\".",
+            stats::STATS_DIR
+        );
+        return;
+    }
+
+    print_table(&days);
+    println!();
+    println!("{ANSI_BOLD}Trends{ANSI_RESET}");
+    print_part_trend(&days, 1);
+    print_part_trend(&days, 2);
+    print_totals(&days);
+}
@@ -0,0 +1,183 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::calendar;
+use advent_of_code::provenance::{self, Status};
+use advent_of_code::puzzle::{self, PuzzleInfo};
+use std::time::SystemTime;
+use std::{fs, process};
+
+const README_PATH: &str = "README.md";
+/// Encloses the results table in the readme, shared with the readme stars workflow.
+const README_MARKER: &str = "<!--- advent_readme_stars table --->";
+
+struct Args {
+    /// print a markdown table for the readme instead.
+    markdown: bool,
+    /// write the markdown table into the readme.
+    readme: bool,
+    /// year of the puzzles downloaded without metadata, the current event's by default.
+    year: Option<u16>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        markdown: args.contains("--markdown"),
+        readme: args.contains("--readme"),
+        year: args.opt_value_from_str(["-y", "--year"])?,
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Part {
+    Solved,
+    Open,
+    Locked,
+}
+
+fn parts(info: &PuzzleInfo) -> [Part; 2] {
+    let part_one = match info.answers.first() {
+        Some(_) => Part::Solved,
+        None => Part::Open,
+    };
+    let part_two = match info.answers.get(1) {
+        Some(_) => Part::Solved,
+        None if info.has_part_two => Part::Open,
+        None => Part::Locked,
+    };
+    [part_one, part_two]
+}
+
+fn format_part(part: Part) -> &'static str {
+    match part {
+        Part::Solved => "⭐",
+        Part::Open => "open",
+        Part::Locked => "locked",
+    }
+}
+
+fn format_input(status: Status) -> &'static str {
+    match status {
+        Status::Missing => "missing",
+        Status::Untracked => "untracked",
+        Status::Unmodified => "ok",
+        Status::Modified => "modified",
+    }
+}
+
+fn print_table(index: &[PuzzleInfo]) {
+    let width = index
+        .iter()
+        .map(|info| info.heading().chars().count())
+        .max()
+        .unwrap_or_default();
+
+    for info in index {
+        let [part_one, part_two] = parts(info);
+        println!(
+            "{:<width$}  part 1: {:<6}  part 2: {:<6}  input: {}",
+            info.heading(),
+            format_part(part_one),
+            format_part(part_two),
+            format_input(provenance::status(info.day)),
+        );
+    }
+}
+
+/// A results table in the format of the readme's, with the puzzle titles included.
+fn markdown_table(index: &[PuzzleInfo], year: Option<u16>) -> String {
+    let fallback_year = year.unwrap_or_else(|| calendar::current_event_year(SystemTime::now()));
+    let year_of = |day| provenance::read(day).map_or(fallback_year, |metadata| metadata.year);
+
+    let mut table = format!("## {} Results\n\n", year_of(index[0].day));
+    table += "| Day | Title | Part 1 | Part 2 |\n";
+    table += "| :---: | :--- | :---: | :---: |\n";
+    for info in index {
+        let stars = parts(info).map(|part| if part == Part::Solved { "⭐" } else { "" });
+        table += &format!(
+            "| [Day {day}](https://adventofcode.com/{year}/day/{day}) | {} | {} | {} |\n",
+            info.title.as_deref().unwrap_or_default(),
+            stars[0],
+            stars[1],
+            day = info.day,
+            year = year_of(info.day),
+        );
+    }
+    table
+}
+
+/// Replaces what is between the two [`README_MARKER`]s in `readme` with `table`.
+fn replace_table(readme: &str, table: &str) -> Option<String> {
+    let (before, rest) = readme.split_once(README_MARKER)?;
+    let (_, after) = rest.split_once(README_MARKER)?;
+    Some(format!(
+        "{before}{README_MARKER}\n{table}{README_MARKER}{after}"
+    ))
+}
+
+fn write_readme(index: &[PuzzleInfo], year: Option<u16>) {
+    let readme = match fs::read_to_string(README_PATH) {
+        Ok(readme) => readme,
+        Err(e) => {
+            eprintln!("Failed to read \"{README_PATH}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    let Some(readme) = replace_table(&readme, &markdown_table(index, year)) else {
+        eprintln!(
+            "\"{README_PATH}\" has no results table, enclose one in two \"{README_MARKER}\" lines."
+        );
+        process::exit(1);
+    };
+
+    match fs::write(README_PATH, readme) {
+        Ok(_) => println!("🎄 Updated the results table in \"{README_PATH}\"."),
+        Err(e) => {
+            eprintln!("Failed to write \"{README_PATH}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    let index = puzzle::index();
+    if index.is_empty() {
+        println!("No puzzles downloaded yet, see `cargo download`.");
+        return;
+    }
+
+    if args.readme {
+        write_readme(&index, args.year);
+    } else if args.markdown {
+        print!("{}", markdown_table(&index, args.year));
+    } else {
+        print_table(&index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_table() {
+        let readme = format!("# Title\n\n{README_MARKER}\n| old |\n{README_MARKER}\n\n---\n");
+        assert_eq!(
+            replace_table(&readme, "| new |\n").unwrap(),
+            format!("# Title\n\n{README_MARKER}\n| new |\n{README_MARKER}\n\n---\n")
+        );
+        assert_eq!(replace_table("# Title\n", "| new |\n"), None);
+        assert_eq!(replace_table(README_MARKER, "| new |\n"), None);
+    }
+}
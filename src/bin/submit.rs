@@ -0,0 +1,106 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::aoc_cli::{self, AocCliError, Backend};
+use advent_of_code::site::SubmitOutcome;
+use advent_of_code::stats::{self, Event};
+use advent_of_code::{calendar, parse_answer, EXEC_TIME_ENV};
+use std::process::{self, Command, Stdio};
+use std::time::SystemTime;
+
+struct Args {
+    day: u8,
+    part: u8,
+    /// `None` submits the answer the solution currently produces.
+    answer: Option<String>,
+    year: Option<u16>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let year = args.opt_value_from_str(["-y", "--year"])?;
+    Ok(Args {
+        day: args.free_from_str()?,
+        part: args.free_from_fn(|val: &str| match val {
+            "1" => Ok(1),
+            "2" => Ok(2),
+            _ => Err(format!("\"{val}\" is not a part, expected 1 or 2")),
+        })?,
+        answer: args.opt_free_from_str()?,
+        year,
+    })
+}
+
+fn exit_with_error(e: &AocCliError) -> ! {
+    eprintln!("{e}");
+    if let Some(hint) = e.hint() {
+        eprintln!("hint: {hint}");
+    }
+    process::exit(1);
+}
+
+/// Runs the solution of `day` and returns its answer to `part`.
+fn solve(day: u8, part: u8) -> Option<String> {
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &format!("{day:02}")])
+        .stderr(Stdio::inherit())
+        .env(EXEC_TIME_ENV, "1")
+        .output()
+        .ok()?;
+
+    parse_answer(&String::from_utf8_lossy(&output.stdout), part)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            eprintln!("usage: `cargo submit <day> <part> [<answer>]`");
+            process::exit(1);
+        }
+    };
+
+    let answer = match args.answer.clone().or_else(|| solve(args.day, args.part)) {
+        Some(answer) => answer,
+        None => {
+            eprintln!(
+                "Day {:02} has no answer to part {}, solve it first or pass the answer.",
+                args.day, args.part
+            );
+            process::exit(1);
+        }
+    };
+
+    let backend = match Backend::detect() {
+        Ok(backend) => backend,
+        Err(e) => exit_with_error(&e),
+    };
+
+    println!(
+        "🎄 Submitting \"{answer}\" for day {:02}, part {}...",
+        args.day, args.part
+    );
+    let outcome = match aoc_cli::submit(&backend, args.day, args.year, args.part, &answer) {
+        Ok(outcome) => outcome,
+        Err(e) => exit_with_error(&e),
+    };
+    println!("🎄 {outcome}");
+
+    if outcome != SubmitOutcome::Correct {
+        process::exit(1);
+    }
+
+    let year = args
+        .year
+        .unwrap_or_else(|| calendar::current_event_year(SystemTime::now()));
+    if let Err(e) = stats::record(year, args.day, Event::Accepted(args.part)) {
+        eprintln!("Failed to record the solve time: {e}");
+    }
+
+    // the puzzle now records the answer, and includes part two after part one.
+    if let Err(e) = aoc_cli::refresh_puzzle(&backend, args.day, args.year) {
+        exit_with_error(&e);
+    }
+}
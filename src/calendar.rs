@@ -0,0 +1,289 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const FIRST_DAY: u8 = 1;
+pub const LAST_DAY: u8 = 25;
+
+/// Puzzles unlock at midnight US Eastern time, which AoC fixes at UTC-5 for the whole event.
+const UNLOCK_HOUR_UTC: u64 = 5;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The instant puzzle `day` of `year` unlocks.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year.into(), 12, day.into());
+    UNIX_EPOCH + Duration::from_secs(days as u64 * SECONDS_PER_DAY + UNLOCK_HOUR_UTC * 60 * 60)
+}
+
+pub fn is_unlocked(year: u16, day: u8, now: SystemTime) -> bool {
+    now >= unlock_time(year, day)
+}
+
+/// The current date `(year, month, day)` in AoC's US Eastern time (UTC-5).
+pub fn eastern_date(now: SystemTime) -> (u16, u8, u8) {
+    let secs = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let secs = secs.saturating_sub(UNLOCK_HOUR_UTC * 60 * 60);
+    let (year, month, day) = civil_from_days((secs / SECONDS_PER_DAY) as i64);
+    (year as u16, month as u8, day as u8)
+}
+
+/// The day whose puzzle unlocked today, if `now` falls within the event.
+pub fn todays_day(now: SystemTime) -> Option<u8> {
+    match eastern_date(now) {
+        (_, 12, day) if day <= LAST_DAY => Some(day),
+        _ => None,
+    }
+}
+
+/// Returns `day` if given, otherwise today's day during the event.
+pub fn day_or_today(day: Option<u8>, now: SystemTime) -> Result<u8, String> {
+    match day.or_else(|| todays_day(now)) {
+        Some(day) => Ok(day),
+        None => Err(format!(
+            "Need to specify a day (as integer), there is no puzzle today. Days default to today's puzzle from December {FIRST_DAY} to {LAST_DAY} (US Eastern time)."
+        )),
+    }
+}
+
+/// Formats `time` as an ISO 8601 UTC timestamp, e.g. `2022-12-01T05:00:00Z`.
+pub fn format_utc(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (year, month, day) = civil_from_days((secs / SECONDS_PER_DAY) as i64);
+    let secs = secs % SECONDS_PER_DAY;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// Parses a timestamp written by [`format_utc`].
+pub fn parse_utc(val: &str) -> Option<SystemTime> {
+    let (date, time) = val.trim().strip_suffix('Z')?.split_once('T')?;
+    let date: Vec<i64> = date
+        .split('-')
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let time: Vec<u64> = time
+        .split(':')
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let (&[year, month, day], &[hours, minutes, seconds]) = (&date[..], &time[..]) else {
+        return None;
+    };
+
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    let secs = days * SECONDS_PER_DAY + hours * 3600 + minutes * 60 + seconds;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Source of the current time, so waiting for an unlock can be tested without waiting.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Blocks until puzzle `day` of `year` is unlocked. `on_tick` receives the remaining time
+/// about once a second while waiting.
+pub fn wait_for_unlock(clock: &impl Clock, year: u16, day: u8, mut on_tick: impl FnMut(Duration)) {
+    let unlock = unlock_time(year, day);
+    while let Ok(remaining) = unlock.duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+        on_tick(remaining);
+        clock.sleep(remaining.min(Duration::from_secs(1)));
+    }
+}
+
+/// Formats a countdown as `HH:MM:SS`, prefixed with the days left if there are any.
+pub fn format_countdown(remaining: Duration) -> String {
+    // round up, so the countdown shows 00:00:00 only once the puzzle is unlocked.
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let (days, secs) = (secs / SECONDS_PER_DAY, secs % SECONDS_PER_DAY);
+    let clock = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {clock}")
+    } else {
+        clock
+    }
+}
+
+/// The year of the most recent event that has started at `now`.
+pub fn current_event_year(now: SystemTime) -> u16 {
+    let secs = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let (year, _, _) = civil_from_days((secs / SECONDS_PER_DAY) as i64);
+    let year = year as u16;
+
+    if is_unlocked(year, FIRST_DAY, now) {
+        year
+    } else {
+        year - 1
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic gregorian date.
+/// see: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Inverse of [`days_from_civil`], returns `(year, month, day)`.
+/// see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct FakeClock(Cell<SystemTime>);
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.0.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.0.set(self.0.get() + duration);
+        }
+    }
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_civil_days_roundtrip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2022, 12, 1), 19327);
+        assert_eq!(civil_from_days(19327), (2022, 12, 1));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        // 2022-12-01T05:00:00Z
+        assert_eq!(unlock_time(2022, 1), at(1_669_870_800));
+        // 2022-12-25T05:00:00Z
+        assert_eq!(unlock_time(2022, 25), at(1_671_944_400));
+    }
+
+    #[test]
+    fn test_is_unlocked() {
+        assert!(!is_unlocked(2022, 1, at(1_669_870_799)));
+        assert!(is_unlocked(2022, 1, at(1_669_870_800)));
+        assert!(!is_unlocked(2022, 2, at(1_669_870_800)));
+    }
+
+    #[test]
+    fn test_current_event_year() {
+        assert_eq!(current_event_year(at(1_669_870_799)), 2021);
+        assert_eq!(current_event_year(at(1_669_870_800)), 2022);
+        // 2023-06-01T00:00:00Z
+        assert_eq!(current_event_year(at(1_685_577_600)), 2022);
+    }
+
+    #[test]
+    fn test_eastern_date() {
+        // 2022-12-01T04:59:59Z is still November 30th in UTC-5.
+        assert_eq!(eastern_date(at(1_669_870_799)), (2022, 11, 30));
+        assert_eq!(eastern_date(at(1_669_870_800)), (2022, 12, 1));
+    }
+
+    #[test]
+    fn test_wait_for_unlock() {
+        let clock = FakeClock(Cell::new(at(1_669_870_800 - 3)));
+        let mut ticks = vec![];
+        wait_for_unlock(&clock, 2022, 1, |remaining| ticks.push(remaining.as_secs()));
+        assert_eq!(ticks, vec![3, 2, 1]);
+        assert_eq!(clock.now(), unlock_time(2022, 1));
+
+        let mut ticks = 0;
+        wait_for_unlock(&clock, 2022, 1, |_| ticks += 1);
+        assert_eq!(ticks, 0);
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(Duration::ZERO), "00:00:00");
+        assert_eq!(format_countdown(Duration::from_millis(500)), "00:00:01");
+        assert_eq!(format_countdown(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86400 + 59)),
+            "2d 00:00:59"
+        );
+    }
+
+    #[test]
+    fn test_todays_day() {
+        assert_eq!(todays_day(at(1_669_870_799)), None);
+        assert_eq!(todays_day(at(1_669_870_800)), Some(1));
+        // 2022-12-25T23:00:00Z
+        assert_eq!(todays_day(at(1_672_009_200)), Some(25));
+        // 2022-12-26T05:00:00Z
+        assert_eq!(todays_day(at(1_672_030_800)), None);
+    }
+
+    #[test]
+    fn test_day_or_today() {
+        assert_eq!(day_or_today(Some(7), at(1_685_577_600)), Ok(7));
+        assert_eq!(day_or_today(None, at(1_669_870_800)), Ok(1));
+        assert!(day_or_today(None, at(1_685_577_600)).is_err());
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc(at(1_669_870_800)), "2022-12-01T05:00:00Z");
+        assert_eq!(format_utc(at(1_672_009_199)), "2022-12-25T22:59:59Z");
+    }
+
+    #[test]
+    fn test_parse_utc() {
+        assert_eq!(parse_utc("2022-12-01T05:00:00Z"), Some(at(1_669_870_800)));
+        assert_eq!(
+            parse_utc(&format_utc(at(1_672_009_199))),
+            Some(at(1_672_009_199))
+        );
+        assert_eq!(parse_utc("2022-12-01 05:00:00"), None);
+        assert_eq!(parse_utc("2022-12-01T05:00Z"), None);
+    }
+}
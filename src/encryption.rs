@@ -0,0 +1,211 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use std::{env, error::Error, fmt::Display, fs, io};
+
+/// Environment variable holding the key for encrypted inputs. Takes precedence over [`KEY_FILE`].
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+/// Local, git-ignored file holding the key for encrypted inputs.
+pub const KEY_FILE: &str = ".aoc-key";
+
+/// Marks encrypted input files and the version of their format.
+const MAGIC: &[u8] = b"aoc-enc-v1\n";
+const NONCE_LEN: usize = 12;
+/// Length of a key in hex digits, 256 bits as created by [`generate_secret`].
+const KEY_HEX_LEN: usize = 64;
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    NoKey,
+    InvalidKey,
+    InvalidFormat,
+    Decryption,
+    Io(io::Error),
+}
+
+impl Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::NoKey => write!(
+                f,
+                "no key for encrypted inputs, set {KEY_ENV} or create \"{KEY_FILE}\"."
+            ),
+            EncryptionError::InvalidKey => write!(
+                f,
+                "key is not {KEY_HEX_LEN} hex digits, create one with `cargo inputs keygen`."
+            ),
+            EncryptionError::InvalidFormat => write!(f, "file is not an encrypted input."),
+            EncryptionError::Decryption => {
+                write!(f, "could not decrypt input, the key does not match.")
+            }
+            EncryptionError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for EncryptionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EncryptionError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for EncryptionError {
+    fn from(e: io::Error) -> Self {
+        EncryptionError::Io(e)
+    }
+}
+
+/// The key in [`KEY_ENV`] or [`KEY_FILE`].
+pub struct InputKey(Key);
+
+impl InputKey {
+    /// Parses a secret as created by [`generate_secret`]. Anything else, like a password, is
+    /// rejected: the secret is used as the key directly, so it must have the key's full entropy.
+    pub fn from_secret(secret: &str) -> Result<Self, EncryptionError> {
+        let secret = secret.trim();
+        if secret.len() != KEY_HEX_LEN || !secret.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(EncryptionError::InvalidKey);
+        }
+
+        let bytes: Vec<u8> = (0..KEY_HEX_LEN)
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&secret[i..i + 2], 16).unwrap())
+            .collect();
+        Ok(InputKey(*Key::from_slice(&bytes)))
+    }
+
+    /// Loads the key from [`KEY_ENV`], falling back to [`KEY_FILE`].
+    pub fn load() -> Result<Self, EncryptionError> {
+        let secret = match env::var(KEY_ENV) {
+            Ok(secret) => secret,
+            Err(_) => match fs::read_to_string(KEY_FILE) {
+                Ok(secret) => secret,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    return Err(EncryptionError::NoKey)
+                }
+                Err(e) => return Err(e.into()),
+            },
+        };
+
+        if secret.trim().is_empty() {
+            return Err(EncryptionError::NoKey);
+        }
+        InputKey::from_secret(&secret)
+    }
+}
+
+/// Generates a random, hex encoded secret suitable for [`KEY_ENV`] or [`KEY_FILE`].
+pub fn generate_secret() -> String {
+    ChaCha20Poly1305::generate_key(&mut OsRng)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+pub fn encrypt(key: &InputKey, plaintext: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(&key.0)
+        .encrypt(&nonce, plaintext)
+        .expect("encryption of an in-memory buffer cannot fail");
+
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+pub fn decrypt(key: &InputKey, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    let data = data
+        .strip_prefix(MAGIC)
+        .filter(|data| data.len() >= NONCE_LEN)
+        .ok_or(EncryptionError::InvalidFormat)?;
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+    ChaCha20Poly1305::new(&key.0)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| EncryptionError::Decryption)
+}
+
+pub fn get_encrypted_path(day: u8) -> String {
+    format!("{}.enc", crate::aoc_cli::get_input_path(day))
+}
+
+/// Reads and decrypts the encrypted input of `day`.
+pub fn read_input(day: u8) -> Result<String, EncryptionError> {
+    let data = fs::read(get_encrypted_path(day))?;
+    let plaintext = decrypt(&InputKey::load()?, &data)?;
+    String::from_utf8(plaintext).map_err(|_| EncryptionError::InvalidFormat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_key() -> InputKey {
+        InputKey::from_secret(&generate_secret()).unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let key = new_key();
+        let encrypted = encrypt(&key, b"1000\n2000\n");
+        assert!(encrypted.starts_with(MAGIC));
+        assert_eq!(decrypt(&key, &encrypted).unwrap(), b"1000\n2000\n");
+    }
+
+    #[test]
+    fn test_nonce_is_random() {
+        let key = new_key();
+        assert_ne!(encrypt(&key, b"input"), encrypt(&key, b"input"));
+    }
+
+    #[test]
+    fn test_decrypt_errors() {
+        let key = new_key();
+        let encrypted = encrypt(&key, b"input");
+        assert!(matches!(
+            decrypt(&new_key(), &encrypted),
+            Err(EncryptionError::Decryption)
+        ));
+        assert!(matches!(
+            decrypt(&key, b"input"),
+            Err(EncryptionError::InvalidFormat)
+        ));
+        assert!(matches!(
+            decrypt(&key, MAGIC),
+            Err(EncryptionError::InvalidFormat)
+        ));
+
+        let mut tampered = encrypted.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            decrypt(&key, &tampered),
+            Err(EncryptionError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn test_secret_is_trimmed() {
+        let secret = generate_secret();
+        let key = InputKey::from_secret(&format!("{secret}\n")).unwrap();
+        let encrypted = encrypt(&key, b"input");
+        assert!(decrypt(&InputKey::from_secret(&secret).unwrap(), &encrypted).is_ok());
+    }
+
+    #[test]
+    fn test_weak_secret_is_rejected() {
+        let secret = generate_secret();
+        let invalid_digit = format!("g{}", &secret[1..]);
+        for weak in ["secret", &secret[1..], &invalid_digit] {
+            assert!(matches!(
+                InputKey::from_secret(weak),
+                Err(EncryptionError::InvalidKey)
+            ));
+        }
+    }
+}
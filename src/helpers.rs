@@ -1,4 +1,19 @@
 /*
  * Use this file if you want to extract helpers from your solutions.
- * Example import from this file: `use advent_of_code::helpers::example_fn;`.
+ * Example import from this file: `use advent_of_code::helpers::Grid;`.
  */
+pub mod cycle;
+pub mod direction;
+pub mod grid;
+pub mod interval;
+pub mod parse;
+pub mod point;
+pub mod search;
+
+pub use cycle::Cycle;
+pub use direction::Direction;
+pub use grid::Grid;
+pub use interval::IntervalSet;
+pub use parse::ParseError;
+pub use point::{Point2, Point3};
+pub use search::Search;
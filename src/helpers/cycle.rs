@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The states a step function runs through from an initial state, which sooner or later
+/// repeat if there are finitely many of them. Any later step can be read off without
+/// simulating it, e.g. the state after a billion rounds.
+#[derive(Clone, Debug)]
+pub struct Cycle<S> {
+    /// The first step that is part of the cycle.
+    pub start: usize,
+    pub length: usize,
+    /// The states from step 0 until just before the cycle repeats.
+    states: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> Cycle<S> {
+    /// Applies `step` to `initial` until a state repeats. Never returns if it does not.
+    pub fn find(initial: S, step: impl FnMut(&S) -> S) -> Self {
+        match simulate(initial, step, usize::MAX) {
+            (states, Some(start)) => Cycle::new(states, start),
+            (_, None) => unreachable!("simulated usize::MAX steps without a cycle"),
+        }
+    }
+
+    fn new(states: Vec<S>, start: usize) -> Self {
+        Cycle {
+            length: states.len() - start,
+            start,
+            states,
+        }
+    }
+
+    /// The state after `n` steps.
+    pub fn state_at(&self, n: usize) -> &S {
+        if n < self.start {
+            &self.states[n]
+        } else {
+            &self.states[self.start + (n - self.start) % self.length]
+        }
+    }
+
+    /// The states before the cycle, followed by one pass through it.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// The state after applying `step` to `initial` `n` times, skipping ahead once the states
+/// start to repeat.
+pub fn state_after<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    match simulate(initial, step, n) {
+        (states, Some(start)) => Cycle::new(states, start).state_at(n).clone(),
+        (mut states, None) => states.swap_remove(n),
+    }
+}
+
+/// Runs up to `limit` steps and returns the states seen, and the step the first repeated
+/// state was seen at if there was one.
+fn simulate<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    limit: usize,
+) -> (Vec<S>, Option<usize>) {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+
+    while states.len() <= limit {
+        let next = step(&states[states.len() - 1]);
+        if let Some(&start) = seen.get(&next) {
+            return (states, Some(start));
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+
+    (states, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn test_find() {
+        // 3, 10, 101, 2, 5, 26, 167, 95, 101, ...
+        let cycle = Cycle::find(3, step);
+        assert_eq!((cycle.start, cycle.length), (2, 6));
+        assert_eq!(cycle.states(), [3, 10, 101, 2, 5, 26, 167, 95]);
+        assert_eq!(*cycle.state_at(1), 10);
+        assert_eq!(*cycle.state_at(8), 101);
+        assert_eq!(*cycle.state_at(9), 2);
+
+        let fixed = Cycle::find(7, |&x| x);
+        assert_eq!((fixed.start, fixed.length), (0, 1));
+        assert_eq!(*fixed.state_at(1_000_000_000), 7);
+    }
+
+    #[test]
+    fn test_state_after() {
+        let mut x = 3;
+        for n in 0..=100 {
+            assert_eq!(state_after(3, step, n), x);
+            x = step(&x);
+        }
+        assert_eq!(
+            state_after(3, step, 1_000_000_000_000),
+            *Cycle::find(3, step).state_at(1_000_000_000_000)
+        );
+    }
+}
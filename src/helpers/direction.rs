@@ -0,0 +1,200 @@
+use std::str::FromStr;
+
+use super::point::Point2;
+
+/// A step on a 2D map, to one of the 8 surrounding cells. Up is towards lower `y`, like the
+/// rows of a puzzle input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// All directions, clockwise from up.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// The orthogonal directions, clockwise from up.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The diagonal directions, clockwise from up right.
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpRight,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpLeft,
+    ];
+
+    pub const fn offset(self) -> Point2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::UpRight => Point2::new(1, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::DownRight => Point2::new(1, 1),
+            Direction::Down => Point2::new(0, 1),
+            Direction::DownLeft => Point2::new(-1, 1),
+            Direction::Left => Point2::new(-1, 0),
+            Direction::UpLeft => Point2::new(-1, -1),
+        }
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        (self as u8) % 2 == 1
+    }
+
+    /// Turns clockwise by `eighths` of a full turn.
+    const fn turn(self, eighths: u8) -> Self {
+        Direction::ALL[((self as u8 + eighths) % 8) as usize]
+    }
+
+    /// Turns by 90 degrees clockwise.
+    pub const fn turn_right(self) -> Self {
+        self.turn(2)
+    }
+
+    /// Turns by 90 degrees counterclockwise.
+    pub const fn turn_left(self) -> Self {
+        self.turn(6)
+    }
+
+    pub const fn turn_around(self) -> Self {
+        self.turn(4)
+    }
+
+    /// Turns by 45 degrees clockwise.
+    pub const fn turn_right_45(self) -> Self {
+        self.turn(1)
+    }
+
+    /// Turns by 45 degrees counterclockwise.
+    pub const fn turn_left_45(self) -> Self {
+        self.turn(7)
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    /// Reads `U/D/L/R`, `N/E/S/W` and `^ v < >`.
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | 'N' | '^' => Ok(Direction::Up),
+            'R' | 'E' | '>' => Ok(Direction::Right),
+            'D' | 'S' | 'v' => Ok(Direction::Down),
+            'L' | 'W' | '<' => Ok(Direction::Left),
+            _ => Err(format!("invalid direction '{c}'")),
+        }
+    }
+}
+
+/// Reads the single characters [`Direction::try_from`] accepts, and the diagonals as
+/// `NE`, `SE`, `SW` and `NW`.
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Direction::UpRight),
+            "SE" => Ok(Direction::DownRight),
+            "SW" => Ok(Direction::DownLeft),
+            "NW" => Ok(Direction::UpLeft),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Direction::try_from(c),
+                    _ => Err(format!("invalid direction \"{s}\"")),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::UpLeft.turn_right(), Direction::UpRight);
+        assert_eq!(Direction::Down.turn_around(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left_45(), Direction::UpLeft);
+        assert_eq!(Direction::UpLeft.turn_right_45(), Direction::Up);
+
+        // turning matches rotating the offset.
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.turn_right().offset(),
+                direction.offset().rotate_cw()
+            );
+            assert_eq!(
+                direction.turn_left().offset(),
+                direction.offset().rotate_ccw()
+            );
+            assert_eq!(direction.turn_around().offset(), -direction.offset());
+        }
+    }
+
+    #[test]
+    fn test_groups() {
+        assert!(Direction::CARDINAL.iter().all(|d| !d.is_diagonal()));
+        assert!(Direction::DIAGONAL.iter().all(|d| d.is_diagonal()));
+        assert!(Direction::DIAGONAL
+            .iter()
+            .all(|d| d.offset().manhattan(Point2::ZERO) == 2));
+    }
+
+    #[test]
+    fn test_parse() {
+        for (input, direction) in [("U", Direction::Up), ("E", Direction::Right)] {
+            assert_eq!(input.parse(), Ok(direction));
+        }
+        assert_eq!(
+            "^>v<"
+                .chars()
+                .map(Direction::try_from)
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(Direction::CARDINAL.to_vec())
+        );
+        assert_eq!(
+            "NWSE"
+                .chars()
+                .map(Direction::try_from)
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(vec![
+                Direction::Up,
+                Direction::Left,
+                Direction::Down,
+                Direction::Right
+            ])
+        );
+        assert_eq!("SW".parse(), Ok(Direction::DownLeft));
+        assert_eq!(
+            "x".parse::<Direction>(),
+            Err("invalid direction 'x'".to_string())
+        );
+        assert!("UU".parse::<Direction>().is_err());
+    }
+}
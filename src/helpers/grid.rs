@@ -0,0 +1,407 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use super::direction::Direction;
+use super::parse::ParseError;
+use super::point::Point2;
+
+/// Offsets of the 4 orthogonal neighbours: up, right, down, left.
+pub const NEIGHBOURS_4: [Point2; 4] = offsets(Direction::CARDINAL);
+
+/// Offsets of the 8 surrounding neighbours, clockwise from up.
+pub const NEIGHBOURS_8: [Point2; 8] = offsets(Direction::ALL);
+
+const fn offsets<const N: usize>(directions: [Direction; N]) -> [Point2; N] {
+    let mut offsets = [Point2::ZERO; N];
+    let mut i = 0;
+    while i < N {
+        offsets[i] = directions[i].offset();
+        i += 1;
+    }
+    offsets
+}
+
+/// A rectangular 2D map, stored row by row. Points outside of it are rejected by the `get`
+/// methods and panic when indexing.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, which must all be equally long.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "row {} has {} cells, expected {width} like the first row",
+                y + 1,
+                rows[y].len()
+            ));
+        }
+
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point2) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y)
+    }
+
+    fn offset(&self, point: Point2) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    fn point(&self, offset: usize) -> Point2 {
+        Point2::new((offset % self.width) as i64, (offset / self.width) as i64)
+    }
+
+    pub fn get(&self, point: Point2) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point2) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// All cells with their points, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(offset, cell)| (self.point(offset), cell))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point2> + '_ {
+        (0..self.cells.len()).map(|offset| self.point(offset))
+    }
+
+    /// The neighbours of `point` that are `offsets` away and inside the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        point: Point2,
+        offsets: &'a [Point2],
+    ) -> impl Iterator<Item = (Point2, &'a T)> {
+        offsets.iter().filter_map(move |&offset| {
+            let neighbour = point + offset;
+            self.get(neighbour).map(|cell| (neighbour, cell))
+        })
+    }
+
+    /// The orthogonal neighbours of `point`, see [`NEIGHBOURS_4`].
+    pub fn neighbours4(&self, point: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        self.neighbours(point, &NEIGHBOURS_4)
+    }
+
+    /// The neighbours of `point` including diagonal ones, see [`NEIGHBOURS_8`].
+    pub fn neighbours8(&self, point: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        self.neighbours(point, &NEIGHBOURS_8)
+    }
+
+    /// The cells of row `y`, left to right.
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(y < self.height, "row {y} is outside of the grid");
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside of the grid");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The cells from `start` on in steps of `step`, e.g. a [`Direction`], as long as they are
+    /// inside the grid.
+    pub fn ray(&self, start: Point2, step: impl Into<Point2>) -> impl Iterator<Item = &T> {
+        let step = step.into();
+        let mut point = start;
+        std::iter::from_fn(move || {
+            let cell = self.get(point)?;
+            point += step;
+            Some(cell)
+        })
+    }
+
+    /// The diagonals running down and to the right, starting from the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height)
+            .rev()
+            .map(|y| Point2::new(0, y as i64))
+            .chain((1..self.width).map(|x| Point2::new(x as i64, 0)));
+        starts.map(|start| self.ray(start, Direction::DownRight))
+    }
+
+    /// The diagonals running down and to the left, starting from the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let last = self.width as i64 - 1;
+        let starts = (0..self.width)
+            .map(|x| Point2::new(x as i64, 0))
+            .chain((1..self.height).map(move |y| Point2::new(last, y as i64)));
+        starts.map(|start| self.ray(start, Direction::DownLeft))
+    }
+
+    /// Points of all cells matching `predicate`, row by row.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point2> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// The first point holding `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Point2> {
+        self.positions(|cell| cell == value).next()
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point2> + 'a {
+        self.positions(move |cell| cell == value)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self
+                .columns()
+                .flat_map(|column| column.rev())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .rev()
+                .flat_map(|x| self.column(x))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Parses a character map, one row per line. All lines must be equally long.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        if let Some(line) = input.lines().find(|line| line.chars().count() != width) {
+            return Err(ParseError::at(
+                input,
+                line,
+                format!("a row of {width} cells"),
+            ));
+        }
+        Ok(Grid::from_rows(input.lines().map(|line| line.chars().collect()).collect()).unwrap())
+    }
+
+    /// Like [`Grid::parse`], but pads short lines with `fill`, e.g. drawings whose trailing
+    /// spaces were trimmed.
+    pub fn parse_padded(input: &str, fill: char) -> Self {
+        let width = input
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default();
+        let rows = input
+            .lines()
+            .map(|line| {
+                let mut row: Vec<char> = line.chars().collect();
+                row.resize(width, fill);
+                row
+            })
+            .collect();
+        Grid::from_rows(rows).unwrap()
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!(
+                "{point} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, point: Point2) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("{point} is outside of the {width}x{height} grid"),
+        }
+    }
+}
+
+/// Renders the grid in the format it was parsed from, one line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#.#\n..#\nS..\n.#.";
+
+    fn collect<'a>(cells: impl Iterator<Item = &'a char>) -> String {
+        cells.collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(MAP).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid[Point2::new(2, 1)], '#');
+        assert_eq!(grid.get(Point2::new(3, 0)), None);
+        assert_eq!(grid.get(Point2::new(0, -1)), None);
+        assert_eq!(grid.to_string(), MAP);
+
+        assert_eq!(
+            Grid::parse("..\n...").unwrap_err().to_string(),
+            "input:2:1: expected a row of 2 cells, found '...'"
+        );
+        assert_eq!(Grid::parse_padded("..\n...", ' ').to_string(), ".. \n...");
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside of the 3x4 grid")]
+    fn test_index_outside() {
+        let _ = Grid::parse(MAP).unwrap()[Point2::new(3, 0)];
+    }
+
+    #[test]
+    #[should_panic(expected = "row 4 is outside of the grid")]
+    fn test_row_outside() {
+        let _ = Grid::parse(MAP).unwrap().row(4);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::parse(MAP).unwrap();
+        let corner: Vec<Point2> = grid
+            .neighbours4(Point2::new(0, 0))
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(corner, [Point2::new(1, 0), Point2::new(0, 1)]);
+        assert_eq!(grid.neighbours4(Point2::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point2::new(1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8(Point2::new(2, 3))
+                .filter(|(_, &c)| c == '#')
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = Grid::parse(MAP).unwrap();
+        assert_eq!(collect(grid.row(2)), "S..");
+        assert_eq!(collect(grid.column(2).rev()), "..##");
+        assert_eq!(
+            grid.columns().map(collect).collect::<Vec<_>>(),
+            ["#.S.", "...#", "##.."]
+        );
+        assert_eq!(
+            grid.diagonals().map(collect).collect::<Vec<_>>(),
+            [".", "S#", "...", "#..", ".#", "#"]
+        );
+        assert_eq!(
+            grid.anti_diagonals().map(collect).collect::<Vec<_>>(),
+            ["#", "..", "#.S", "#..", ".#", "."]
+        );
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = Grid::parse("ab\ncd\nef").unwrap();
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf");
+        assert_eq!(grid.rotate_cw().to_string(), "eca\nfdb");
+        assert_eq!(grid.rotate_ccw().to_string(), "bdf\nace");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+
+    #[test]
+    fn test_find() {
+        let mut grid = Grid::parse(MAP).unwrap();
+        assert_eq!(grid.find(&'S'), Some(Point2::new(0, 2)));
+        assert_eq!(grid.find_all(&'#').count(), 4);
+
+        grid[Point2::new(0, 2)] = '.';
+        assert_eq!(grid.find(&'S'), None);
+        assert_eq!(grid.map(|&c| c == '#').find_all(&true).count(), 4);
+    }
+}
@@ -0,0 +1,275 @@
+use std::fmt::Debug;
+use std::ops::{Add, RangeInclusive, Sub};
+
+/// The primitive integer types, for [`IntervalSet`].
+pub trait Integer: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ONE: Self;
+    const MAX: Self;
+
+    /// The number of steps from `self` up to `end`, which must not be lower. Fits in a `u128`
+    /// for every type, even where it does not fit in `Self`.
+    fn steps_to(self, end: Self) -> u128;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),+) => {
+        $(impl Integer for $t {
+            const ONE: Self = 1;
+            const MAX: Self = <$t>::MAX;
+
+            fn steps_to(self, end: Self) -> u128 {
+                // exact modulo 2^128, and the difference is below that.
+                (end as i128).wrapping_sub(self as i128) as u128
+            }
+        })+
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A set of integers stored as sorted, disjoint ranges, so operations take time in the number
+/// of ranges rather than the number of values. Adjacent ranges are merged, `1..=2` and
+/// `3..=4` are stored as `1..=4`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// `(start, end)` pairs, both included.
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+
+    /// Adds `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // the ranges before `first` end before `start` without touching it, those from
+        // `last` on start after `end`.
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e < start && e + T::ONE < start);
+        let last = self
+            .ranges
+            .partition_point(|&(s, _)| s <= end || (end < T::MAX && s == end + T::ONE));
+
+        if let Some(&(s, _)) = self.ranges.get(first).filter(|_| first < last) {
+            start = start.min(s);
+            end = end.max(self.ranges[last - 1].1);
+        }
+        self.ranges.splice(first..last, [(start, end)]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, end)| end < value);
+        self.ranges.get(i).is_some_and(|&(start, _)| start <= value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of values in the set, which may be more than `T` can hold. Panics if the set
+    /// holds every value of a 128-bit type, one more than a `u128` can count.
+    pub fn len(&self) -> u128 {
+        self.ranges.iter().fold(0, |len, &(start, end)| {
+            start
+                .steps_to(end)
+                .checked_add(1)
+                .and_then(|n| len.checked_add(n))
+                .expect("the set holds more than u128::MAX values")
+        })
+    }
+
+    /// The disjoint ranges making up the set, in ascending order.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// The ranges between the ones in the set, from its lowest to its highest value.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges
+            .windows(2)
+            .map(|pair| pair[0].1 + T::ONE..=pair[1].0 - T::ONE)
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges: Vec<(T, T)> = Vec::with_capacity(self.ranges.len() + other.ranges.len());
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        // merge both by start, joining each range to the previous one if they touch.
+        while let Some(&(start, end)) = match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if y.0 < x.0 => b.next(),
+            (Some(_), _) => a.next(),
+            (None, _) => b.next(),
+        } {
+            match ranges.last_mut() {
+                Some(last) if last.1 >= start || last.1 + T::ONE == start => {
+                    last.1 = last.1.max(end)
+                }
+                _ => ranges.push((start, end)),
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) =
+            (self.ranges.get(i), other.ranges.get(j))
+        {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // the range ending first cannot overlap anything after the other one.
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    /// The values in `self` but not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = vec![];
+        let mut j = 0;
+
+        for &(start, end) in &self.ranges {
+            // skip the ranges of `other` that end before this one.
+            while other.ranges.get(j).is_some_and(|&(_, e)| e < start) {
+                j += 1;
+            }
+
+            // the lowest value that is left after the cuts so far.
+            let mut rest = Some(start);
+            let cuts = other.ranges[j..].iter().take_while(|&&(s, _)| s <= end);
+            for &(cut_start, cut_end) in cuts {
+                let Some(s) = rest else { break };
+                if cut_start > s {
+                    ranges.push((s, cut_start - T::ONE));
+                }
+                rest = (cut_end < end).then(|| cut_end + T::ONE);
+            }
+            if let Some(s) = rest {
+                ranges.push((s, end));
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn is_subset(&self, other: &IntervalSet<T>) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn is_disjoint(&self, other: &IntervalSet<T>) -> bool {
+        self.intersection(other).is_empty()
+    }
+}
+
+impl<T: Integer> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges<T: Integer>(set: &IntervalSet<T>) -> Vec<RangeInclusive<T>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set = IntervalSet::new();
+        set.insert(10..=12);
+        set.insert(1..=3);
+        set.insert(20..=25);
+        assert_eq!(ranges(&set), vec![1..=3, 10..=12, 20..=25]);
+
+        // touching ranges are merged, overlapping ones too.
+        set.insert(4..=5);
+        set.insert(11..=21);
+        assert_eq!(ranges(&set), vec![1..=5, 10..=25]);
+
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(8..=7);
+        set.insert(0..=30);
+        assert_eq!(ranges(&set), vec![0..=30]);
+
+        let mut edges: IntervalSet<u8> = [250..=255, 0..=0, 1..=3].into_iter().collect();
+        edges.insert(240..=249);
+        assert_eq!(ranges(&edges), vec![0..=3, 240..=255]);
+        let full = edges.union(&(4..=250).into());
+        assert_eq!(ranges(&full), vec![0..=255]);
+        assert_eq!(full.len(), 256);
+    }
+
+    #[test]
+    fn test_queries() {
+        let set: IntervalSet<i64> = [-5..=-1, 3..=3, 10..=19].into_iter().collect();
+        assert_eq!(set.len(), 16);
+        assert!(set.contains(-5) && set.contains(3) && set.contains(19));
+        assert!(!set.contains(0) && !set.contains(20));
+        assert_eq!(set.gaps().collect::<Vec<_>>(), vec![0..=2, 4..=9]);
+        assert!(IntervalSet::<u32>::new().is_empty());
+        assert_eq!(IntervalSet::<u32>::new().len(), 0);
+
+        // the length is counted beyond what the type can hold.
+        assert_eq!(IntervalSet::<i8>::from(-100..=100).len(), 201);
+        assert_eq!(IntervalSet::from(i64::MIN..=i64::MAX).len(), 1 << 64);
+        let set: IntervalSet<u128> = [0..=0, 2..=u128::MAX].into_iter().collect();
+        assert_eq!(set.len(), u128::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "more than u128::MAX values")]
+    fn test_len_overflow() {
+        IntervalSet::from(i128::MIN..=i128::MAX).len();
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<u32> = [1..=5, 10..=15].into_iter().collect();
+        let b: IntervalSet<u32> = [4..=11, 14..=14, 20..=22].into_iter().collect();
+
+        assert_eq!(ranges(&a.union(&b)), vec![1..=15, 20..=22]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![4..=5, 10..=11, 14..=14]);
+        assert_eq!(ranges(&a.difference(&b)), vec![1..=3, 12..=13, 15..=15]);
+        assert_eq!(ranges(&b.difference(&a)), vec![6..=9, 20..=22]);
+        assert_eq!(a.difference(&a), IntervalSet::new());
+
+        assert!(IntervalSet::from(2..=4).is_subset(&a));
+        assert!(!IntervalSet::from(4..=10).is_subset(&a));
+        assert!(a.is_disjoint(&IntervalSet::from(6..=9)));
+        assert!(!a.is_disjoint(&b));
+    }
+}
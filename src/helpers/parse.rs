@@ -0,0 +1,359 @@
+use std::any;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Malformed input, located by the 1-based line and column of the offending text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    /// An error about `found`, a slice of `text`, located relative to `text`.
+    pub fn at(text: &str, found: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position(text, found);
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// Relocates an error located relative to `inner`, a slice of `outer`, to be relative to
+    /// `outer`. Parsers locate errors in the text they are given, callers place them in theirs.
+    pub fn within(self, outer: &str, inner: &str) -> Self {
+        let (line, column) = position(outer, inner);
+        ParseError {
+            column: if self.line == 1 {
+                column + self.column - 1
+            } else {
+                self.column
+            },
+            line: line + self.line - 1,
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "input:{}:{}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        match self.found.as_str() {
+            "" => write!(f, "end of line"),
+            found => write!(f, "'{found}'"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// The line and column `part` starts at in `text`. `part` has to be a slice of `text`,
+/// otherwise the start of `text` is returned.
+fn position(text: &str, part: &str) -> (usize, usize) {
+    let start = text.as_ptr() as usize;
+    let offset = (part.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|&offset| offset <= text.len() && text.is_char_boundary(offset))
+        .unwrap_or_default();
+
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+/// The name of `T` without its module path, e.g. `u32` or `String`.
+fn type_label<T>() -> String {
+    let name = any::type_name::<T>();
+    format!("`{}`", name.rsplit("::").next().unwrap_or(name))
+}
+
+/// Parses every line of `input` with `f`, locating errors in `input`.
+pub fn parse_lines<T>(
+    input: &str,
+    mut f: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| f(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+/// Reads `field`, a slice of `line`, as a single character within `range`.
+pub fn char_in(line: &str, field: &str, range: RangeInclusive<char>) -> Result<char, ParseError> {
+    let mut chars = field.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if range.contains(&c) => Ok(c),
+        _ => Err(ParseError::at(
+            line,
+            field,
+            format!("{:?}..{:?}", range.start(), range.end()),
+        )),
+    }
+}
+
+/// Converts CRLF line endings to LF and removes trailing line breaks, so inputs saved on any
+/// platform parse the same.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let trimmed = input.trim_end_matches(['\r', '\n']);
+    if trimmed.contains('\r') {
+        Cow::Owned(trimmed.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(trimmed)
+    }
+}
+
+/// Splits `input` into the blocks separated by blank lines. Line endings may be LF or CRLF,
+/// and the blocks do not include their trailing line break.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    let mut blocks = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']).is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\r', '\n']).len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        blocks.push(&input[start..end]);
+    }
+
+    blocks.into_iter()
+}
+
+/// Extracts all unsigned integers from `line`, ignoring everything around them. A `-` is not
+/// read as a sign, so ranges like `2-4` yield `[2, 4]`.
+pub fn unsigned<T: FromStr>(line: &str) -> Result<Vec<T>, ParseError> {
+    numbers(line, false)
+}
+
+/// Extracts all integers from `line`, ignoring everything around them. A `-` right before a
+/// number is read as its sign unless it follows a digit, so `x=-3` yields `[-3]` but `2-4`
+/// yields `[2, 4]`.
+pub fn signed<T: FromStr>(line: &str) -> Result<Vec<T>, ParseError> {
+    numbers(line, true)
+}
+
+fn numbers<T: FromStr>(line: &str, signed: bool) -> Result<Vec<T>, ParseError> {
+    let bytes = line.as_bytes();
+    let mut numbers = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut start = i;
+        if signed && start > 0 && bytes[start - 1] == b'-' {
+            let after_digit = start > 1 && bytes[start - 2].is_ascii_digit();
+            if !after_digit {
+                start -= 1;
+            }
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        numbers.push(parse_field(line, &line[start..i])?);
+    }
+
+    Ok(numbers)
+}
+
+/// Values that can be built from the fields a [`pattern`] captured, implemented for tuples
+/// of up to six [`FromStr`] values.
+pub trait FromFields: Sized {
+    const LEN: usize;
+
+    /// Parses `fields`, which are slices of `line`.
+    fn from_fields(line: &str, fields: &[&str]) -> Result<Self, ParseError>;
+}
+
+/// Parses `field`, a slice of `line`.
+pub fn parse_field<T: FromStr>(line: &str, field: &str) -> Result<T, ParseError> {
+    field
+        .parse()
+        .map_err(|_| ParseError::at(line, field, type_label::<T>()))
+}
+
+macro_rules! impl_from_fields {
+    ($len:expr; $($t:ident $i:tt),+) => {
+        impl<$($t: FromStr),+> FromFields for ($($t,)+) {
+            const LEN: usize = $len;
+
+            fn from_fields(line: &str, fields: &[&str]) -> Result<Self, ParseError> {
+                Ok(($(parse_field::<$t>(line, fields[$i])?,)+))
+            }
+        }
+    };
+}
+
+impl_from_fields!(1; A 0);
+impl_from_fields!(2; A 0, B 1);
+impl_from_fields!(3; A 0, B 1, C 2);
+impl_from_fields!(4; A 0, B 1, C 2, D 3);
+impl_from_fields!(5; A 0, B 1, C 2, D 3, E 4);
+impl_from_fields!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// Parses `line` according to `pattern`, where every `{}` captures a field up to the text
+/// following it: `pattern::<(u32, usize, usize)>("move {} from {} to {}", line)`.
+pub fn pattern<T: FromFields>(pattern: &str, line: &str) -> Result<T, ParseError> {
+    let fields = fields(pattern, line)?;
+    assert_eq!(
+        fields.len(),
+        T::LEN,
+        "pattern \"{pattern}\" has {} fields, expected {}",
+        fields.len(),
+        T::LEN
+    );
+    T::from_fields(line, &fields)
+}
+
+/// Like [`pattern`], but returns the captured fields as slices of `line`.
+pub fn fields<'a>(pattern: &str, line: &'a str) -> Result<Vec<&'a str>, ParseError> {
+    // points at the next `len` characters of `rest`, for errors.
+    let next = |rest: &'a str, len: usize| {
+        let end = rest.char_indices().nth(len).map_or(rest.len(), |(i, _)| i);
+        &rest[..end]
+    };
+
+    let mut literals = pattern.split("{}");
+    let first = literals.next().unwrap_or_default();
+    let mut rest = line.strip_prefix(first).ok_or_else(|| {
+        ParseError::at(
+            line,
+            next(line, first.chars().count()),
+            format!("{first:?}"),
+        )
+    })?;
+    let mut fields = vec![];
+
+    for literal in literals {
+        let end = if literal.is_empty() {
+            // the last field takes the rest of the line, others need a separator.
+            rest.len()
+        } else {
+            rest.find(literal)
+                .ok_or_else(|| ParseError::at(line, &rest[rest.len()..], format!("{literal:?}")))?
+        };
+        fields.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+
+    if !rest.is_empty() {
+        return Err(ParseError::at(line, rest, "end of line"));
+    }
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("a\r\nb\r\n\r\n"), "a\nb");
+        assert_eq!(normalize("a\nb\n"), "a\nb");
+        assert!(matches!(normalize("a\nb\n"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(blocks("1\n2\n\n3\n").collect::<Vec<_>>(), vec!["1\n2", "3"]);
+        assert_eq!(
+            blocks("1\r\n2\r\n\r\n\r\n3").collect::<Vec<_>>(),
+            vec!["1\r\n2", "3"]
+        );
+        assert_eq!(
+            blocks("\n  [D]\n\nmove").collect::<Vec<_>>(),
+            vec!["  [D]", "move"]
+        );
+        assert_eq!(blocks("").count(), 0);
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(unsigned::<u32>("2-4,6-8"), Ok(vec![2, 4, 6, 8]));
+        assert_eq!(signed::<i64>("x=-3, y=10 and 2-4"), Ok(vec![-3, 10, 2, 4]));
+        assert_eq!(unsigned::<u8>("no numbers"), Ok(vec![]));
+        assert_eq!(
+            unsigned::<u8>("1, 300").unwrap_err().to_string(),
+            "input:1:4: expected `u8`, found '300'"
+        );
+    }
+
+    #[test]
+    fn test_pattern() {
+        assert_eq!(
+            pattern::<(u32, usize, usize)>("move {} from {} to {}", "move 12 from 2 to 3"),
+            Ok((12, 2, 3))
+        );
+        assert_eq!(pattern::<(char, char)>("{} {}", "A Y"), Ok(('A', 'Y')));
+        assert_eq!(fields("{}: {}", "a: b: -4"), Ok(vec!["a", "b: -4"]));
+
+        let error = |pattern, line| fields(pattern, line).unwrap_err().to_string();
+        assert_eq!(
+            error("move {} to {}", "go 1 to 2"),
+            "input:1:1: expected \"move \", found 'go 1 '"
+        );
+        assert_eq!(
+            error("{}-{}", "2,4"),
+            "input:1:4: expected \"-\", found end of line"
+        );
+        assert_eq!(
+            error("{}-{},", "2-4,6-8"),
+            "input:1:5: expected end of line, found '6-8'"
+        );
+        assert_eq!(
+            pattern::<(String, i32)>("{}: {}", "a: b: -4")
+                .unwrap_err()
+                .to_string(),
+            "input:1:4: expected `i32`, found 'b: -4'"
+        );
+    }
+
+    #[test]
+    fn test_locate_errors() {
+        let input = "A Y\nB X\nC D\n";
+        let error = parse_lines(input, |line| {
+            let fields = fields("{} {}", line)?;
+            Ok((
+                char_in(line, fields[0], 'A'..='C')?,
+                char_in(line, fields[1], 'X'..='Z')?,
+            ))
+        })
+        .unwrap_err();
+        assert_eq!(error.to_string(), "input:3:3: expected 'X'..'Z', found 'D'");
+
+        let block = &input[4..];
+        let error = ParseError::at(block, &block[6..], "'X'").within(input, block);
+        assert_eq!((error.line, error.column), (3, 3));
+    }
+}
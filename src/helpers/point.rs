@@ -0,0 +1,221 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use super::direction::Direction;
+
+/// A position on a 2D map. `y` grows downwards, like the rows of a puzzle input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A position in 3D space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point2 {
+    pub const ZERO: Point2 = Point2::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Point2) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Rotates by 90 degrees clockwise around the origin, as seen on the map.
+    pub const fn rotate_cw(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+
+    /// Rotates by 90 degrees counterclockwise around the origin, as seen on the map.
+    pub const fn rotate_ccw(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+
+    /// The point with each coordinate reduced to -1, 0 or 1, e.g. one step towards another.
+    pub const fn signum(self) -> Self {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl Point3 {
+    pub const ZERO: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// Rotates by 90 degrees around the x axis, turning y into z.
+    pub const fn rotate_x(self) -> Self {
+        Point3::new(self.x, -self.z, self.y)
+    }
+
+    /// Rotates by 90 degrees around the y axis, turning z into x.
+    pub const fn rotate_y(self) -> Self {
+        Point3::new(self.z, self.y, -self.x)
+    }
+
+    /// Rotates by 90 degrees around the z axis, turning x into y.
+    pub const fn rotate_z(self) -> Self {
+        Point3::new(-self.y, self.x, self.z)
+    }
+
+    pub const fn signum(self) -> Self {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($c:ident),+ }) => {
+        impl Add for $point {
+            type Output = $point;
+
+            fn add(self, other: $point) -> $point {
+                $point { $($c: self.$c + other.$c),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+
+            fn sub(self, other: $point) -> $point {
+                $point { $($c: self.$c - other.$c),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = $point;
+
+            fn mul(self, factor: i64) -> $point {
+                $point { $($c: self.$c * factor),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> $point {
+                $point { $($c: -self.$c),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl Add<Direction> for Point2 {
+    type Output = Point2;
+
+    fn add(self, direction: Direction) -> Point2 {
+        self + direction.offset()
+    }
+}
+
+impl AddAssign<Direction> for Point2 {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction;
+    }
+}
+
+impl From<Direction> for Point2 {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl Display for Point2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point2() {
+        let mut point = Point2::new(3, -2);
+        assert_eq!(point + Point2::new(1, 1), Point2::new(4, -1));
+        assert_eq!(point - Point2::new(1, 1), Point2::new(2, -3));
+        assert_eq!(point * 3, Point2::new(9, -6));
+        assert_eq!(-point, Point2::new(-3, 2));
+        assert_eq!(point.manhattan(Point2::ZERO), 5);
+        assert_eq!(point.signum(), Point2::new(1, -1));
+        assert_eq!(point + Direction::Down, Point2::new(3, -1));
+
+        point += Direction::Left;
+        point -= Point2::new(0, 1);
+        assert_eq!(point, (2, -3).into());
+        assert_eq!(point.to_string(), "(2, -3)");
+    }
+
+    #[test]
+    fn test_point2_rotation() {
+        // up turns right, as on the map.
+        let up = Point2::new(0, -1);
+        assert_eq!(up.rotate_cw(), Point2::new(1, 0));
+        assert_eq!(up.rotate_ccw(), Point2::new(-1, 0));
+
+        let point = Point2::new(3, 1);
+        assert_eq!(point.rotate_cw().rotate_ccw(), point);
+        assert_eq!(point.rotate_cw().rotate_cw(), -point);
+    }
+
+    #[test]
+    fn test_point3() {
+        let point = Point3::new(1, 2, 3);
+        assert_eq!(point + Point3::new(1, 1, 1), Point3::new(2, 3, 4));
+        assert_eq!(point - point, Point3::ZERO);
+        assert_eq!(point.manhattan(Point3::new(-1, 2, 0)), 5);
+        assert_eq!(point.rotate_z(), Point3::new(-2, 1, 3));
+        assert_eq!(point.rotate_x(), Point3::new(1, -3, 2));
+        assert_eq!(point.rotate_y(), Point3::new(3, 2, -1));
+        for rotate in [Point3::rotate_x, Point3::rotate_y, Point3::rotate_z] {
+            assert_eq!(rotate(rotate(rotate(rotate(point)))), point);
+        }
+        assert_eq!(point.to_string(), "(1, 2, 3)");
+    }
+}
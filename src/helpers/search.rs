@@ -0,0 +1,291 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The result of a search from `start`: the distance to every node it reached and the node
+/// each was reached from. Nodes are anything hashable, e.g. grid points, puzzle states or
+/// the keys of an adjacency list.
+///
+/// If [`dijkstra`] or [`astar`] stopped at a goal, only the distances and paths of the goal and
+/// the nodes expanded before it are final, for [`astar`] only if the heuristic never drops by
+/// more than the cost of an edge. Nodes that were still queued hold the best distance found so
+/// far, which may be longer than the shortest one. Searches that explore everything, and
+/// [`bfs`], have no such nodes.
+#[derive(Clone, Debug)]
+pub struct Search<N, C> {
+    pub start: N,
+    /// The goal the search stopped at, if any.
+    pub goal: Option<N>,
+    /// The distances to the reached nodes, see above for which ones are final.
+    pub distances: HashMap<N, C>,
+    pub predecessors: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Search {
+            distances: HashMap::from([(start.clone(), zero)]),
+            predecessors: HashMap::new(),
+            goal: None,
+            start,
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// The nodes from `start` to `node`, both included.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        let mut current = node;
+        while let Some(previous) = self.predecessors.get(current) {
+            path.push(previous.clone());
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        self.path(self.goal.as_ref()?)
+    }
+}
+
+/// Breadth-first search from `start`, counting every edge `neighbours` returns as one step.
+/// Stops at the first node `is_goal` accepts, pass `|_| false` to explore everything.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        let distance = search.distances[&node] + 1;
+        for next in neighbours(&node) {
+            if search.distances.contains_key(&next) {
+                continue;
+            }
+            search.distances.insert(next.clone(), distance);
+            search.predecessors.insert(next.clone(), node.clone());
+            queue.push_back(next);
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm from `start`, where `neighbours` returns each neighbour with the cost
+/// of the edge to it. Costs must not be negative. Stops like [`bfs`], leaving the distances of
+/// the nodes still queued unfinished, see [`Search`].
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search from `start`, like [`dijkstra`] but guided by `heuristic`, which estimates the
+/// cost from a node to the goal. The distance to the goal is only guaranteed to be the
+/// shortest if the estimate never exceeds the real cost.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start.clone(), C::default());
+    let mut queue = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        // the node was queued again with a lower cost since.
+        if cost > search.distances[&node] {
+            continue;
+        }
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if search
+                .distances
+                .get(&next)
+                .is_some_and(|&known| known <= next_cost)
+            {
+                continue;
+            }
+            search.distances.insert(next.clone(), next_cost);
+            search.predecessors.insert(next.clone(), node.clone());
+            queue.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+
+    search
+}
+
+/// A node in the priority queue, ordered so the lowest priority is popped first.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{Grid, Point2};
+
+    const MAZE: &str = "S.#.\n..#.\n#...\n..#E";
+
+    fn open_neighbours(grid: &Grid<char>, point: Point2) -> Vec<Point2> {
+        grid.neighbours4(point)
+            .filter(|(_, &cell)| cell != '#')
+            .map(|(next, _)| next)
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs_grid() {
+        let grid = Grid::parse(MAZE).unwrap();
+        let end = grid.find(&'E').unwrap();
+        let search = bfs(
+            grid.find(&'S').unwrap(),
+            |&point| open_neighbours(&grid, point),
+            |&point| point == end,
+        );
+
+        assert_eq!(search.goal, Some(end));
+        assert_eq!(search.goal_distance(), Some(6));
+        let path = search.goal_path().unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&Point2::new(0, 0)));
+        assert!(path.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+    }
+
+    #[test]
+    fn test_bfs_explores_everything() {
+        // an implicit state space: from n, go to n + 1 or 2n.
+        let search = bfs(
+            1u32,
+            |&n| [n + 1, n * 2].into_iter().filter(|&n| n <= 20),
+            |_| false,
+        );
+        assert_eq!(search.goal, None);
+        assert_eq!(search.distances.len(), 20);
+        assert_eq!(search.distance(&20), Some(5));
+        assert_eq!(search.path(&20), Some(vec![1, 2, 4, 5, 10, 20]));
+        assert_eq!(search.path(&21), None);
+        assert_eq!(search.path(&1), Some(vec![1]));
+    }
+
+    #[test]
+    fn test_dijkstra_adjacency_list() {
+        let edges: HashMap<&str, Vec<(&str, u32)>> = HashMap::from([
+            ("a", vec![("b", 7), ("c", 9), ("f", 14)]),
+            ("b", vec![("c", 10), ("d", 15)]),
+            ("c", vec![("d", 11), ("f", 2)]),
+            ("d", vec![("e", 6)]),
+            ("f", vec![("e", 9)]),
+        ]);
+        let search = dijkstra(
+            "a",
+            |node| edges.get(node).cloned().unwrap_or_default(),
+            |_| false,
+        );
+
+        assert_eq!(search.distance(&"e"), Some(20));
+        assert_eq!(search.path(&"e"), Some(vec!["a", "c", "f", "e"]));
+        assert_eq!(search.predecessors.get("d"), Some(&"c"));
+        assert_eq!(search.distance(&"d"), Some(20));
+    }
+
+    #[test]
+    fn test_astar_grid() {
+        let grid = Grid::parse(MAZE).unwrap();
+        let end = grid.find(&'E').unwrap();
+        let manhattan = |point: &Point2| point.manhattan(end);
+        let search = astar(
+            grid.find(&'S').unwrap(),
+            |&point| {
+                open_neighbours(&grid, point)
+                    .into_iter()
+                    .map(|next| (next, 1i64))
+            },
+            manhattan,
+            |&point| point == end,
+        );
+
+        assert_eq!(search.goal_distance(), Some(6));
+        assert_eq!(search.goal_path().unwrap().last(), Some(&end));
+
+        let unreachable = astar(
+            Point2::new(0, 0),
+            |_| Vec::<(Point2, i64)>::new(),
+            manhattan,
+            |&point| point == end,
+        );
+        assert_eq!(unreachable.goal, None);
+        assert_eq!(unreachable.goal_path(), None);
+    }
+}
@@ -0,0 +1,177 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs, io,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::calendar;
+use crate::site::{Client, SiteError};
+
+/// Environment variable holding the id of the private leaderboard to show.
+pub const LEADERBOARD_ID_ENV: &str = "AOC_LEADERBOARD_ID";
+
+/// Where fetched leaderboards are cached. The site asks not to request them more often than
+/// every [`MIN_REFRESH_INTERVAL`].
+pub const CACHE_DIR: &str = ".aoc-cache";
+pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// A private leaderboard, as served by `/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Members without a public name are shown as anonymous.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: u64,
+    /// Days to parts to the time the star was earned.
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Members by local score, then by who got their last star first, as on the site.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+
+    pub fn member(&self, id: u64) -> Option<&Member> {
+        self.members.values().find(|member| member.id == id)
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// When the star for `part` of `day` was earned.
+    pub fn star_time(&self, day: u8, part: u8) -> Option<SystemTime> {
+        let star = self
+            .completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())?;
+        Some(UNIX_EPOCH + Duration::from_secs(star.get_star_ts))
+    }
+
+    /// How long after the puzzle unlocked the star for `part` of `day` was earned.
+    pub fn solve_time(&self, year: u16, day: u8, part: u8) -> Option<Duration> {
+        let earned = self.star_time(day, part)?;
+        earned.duration_since(calendar::unlock_time(year, day)).ok()
+    }
+
+    pub fn stars_on(&self, day: u8) -> usize {
+        (1..=2)
+            .filter(|&part| self.star_time(day, part).is_some())
+            .count()
+    }
+}
+
+pub fn get_cache_path(year: u16, id: u64) -> String {
+    format!("{CACHE_DIR}/leaderboard-{year}-{id}.json")
+}
+
+/// The last copy cached before today's puzzle unlocked, kept to show how scores changed since.
+pub fn get_previous_cache_path(year: u16, id: u64) -> String {
+    format!("{CACHE_DIR}/leaderboard-{year}-{id}.previous.json")
+}
+
+/// Fetches the raw JSON of leaderboard `id`, so it can be cached as served.
+pub fn fetch(client: &Client, year: u16, id: u64) -> Result<String, SiteError> {
+    client.get(&format!("/{year}/leaderboard/private/view/{id}.json"))
+}
+
+/// The cached JSON of leaderboard `id`, along with when it was fetched.
+pub fn read_cache(year: u16, id: u64) -> Option<(String, SystemTime)> {
+    let path = get_cache_path(year, id);
+    let fetched_at = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+    let json = fs::read_to_string(&path).ok()?;
+    Some((json, fetched_at))
+}
+
+/// Caches JSON fetched at `now`. The copy it replaces becomes the previous one if it was fetched
+/// before today's puzzle unlocked, so fetches later the same day compare against the day before.
+pub fn write_cache(year: u16, id: u64, json: &str, now: SystemTime) -> io::Result<()> {
+    let path = get_cache_path(year, id);
+    fs::create_dir_all(CACHE_DIR)?;
+    if let Some((_, fetched_at)) = read_cache(year, id) {
+        if calendar::eastern_date(fetched_at) < calendar::eastern_date(now) {
+            fs::rename(&path, get_previous_cache_path(year, id))?;
+        }
+    }
+    fs::write(path, json)
+}
+
+pub fn read_previous(year: u16, id: u64) -> Option<Leaderboard> {
+    let json = fs::read_to_string(get_previous_cache_path(year, id)).ok()?;
+    Leaderboard::parse(&json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{"event":"2022","owner_id":1,"members":{
+        "1":{"id":1,"name":"Alice","stars":3,"local_score":10,"global_score":0,"last_star_ts":1669960000,
+            "completion_day_level":{"1":{"1":{"get_star_ts":1669871100,"star_index":0},"2":{"get_star_ts":1669872300,"star_index":1}},"2":{"1":{"get_star_ts":1669958500,"star_index":2}}}},
+        "2":{"id":2,"name":null,"stars":2,"local_score":4,"global_score":0,"last_star_ts":1669880000,
+            "completion_day_level":{"1":{"1":{"get_star_ts":1669875600,"star_index":0},"2":{"get_star_ts":1669880000,"star_index":1}}}},
+        "3":{"id":3,"name":"Carol","stars":0,"local_score":4,"global_score":0,"last_star_ts":0,"completion_day_level":{}}}}"#;
+
+    #[test]
+    fn test_ranked() {
+        let leaderboard = Leaderboard::parse(JSON).unwrap();
+        let ranked: Vec<u64> = leaderboard.ranked().iter().map(|m| m.id).collect();
+        assert_eq!(ranked, vec![1, 3, 2]);
+        assert_eq!(
+            leaderboard.member(2).unwrap().display_name(),
+            "(anonymous user #2)"
+        );
+    }
+
+    #[test]
+    fn test_stars() {
+        let leaderboard = Leaderboard::parse(JSON).unwrap();
+        let alice = leaderboard.member(1).unwrap();
+        assert_eq!(alice.stars_on(1), 2);
+        assert_eq!(alice.stars_on(2), 1);
+        assert_eq!(alice.stars_on(3), 0);
+
+        // day 1 of 2022 unlocked at 1669870800.
+        assert_eq!(alice.solve_time(2022, 1, 1), Some(Duration::from_secs(300)));
+        assert_eq!(
+            alice.solve_time(2022, 1, 2),
+            Some(Duration::from_secs(1500))
+        );
+        assert_eq!(alice.solve_time(2022, 2, 2), None);
+    }
+}
@@ -4,231 +4,356 @@
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
 use std::env;
+use std::fmt::Display;
 use std::fs;
-
+use std::path::Path;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub mod answers;
+pub mod aoc_cli;
+pub mod calendar;
+pub mod encryption;
 pub mod helpers;
+pub mod leaderboard;
+pub mod profile;
+pub mod provenance;
+pub mod puzzle;
+pub mod site;
+pub mod stats;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Environment variable holding the time limit for a single part, e.g. `AOC_TIMEOUT=5s`.
+pub const TIMEOUT_ENV: &str = "AOC_TIMEOUT";
+
+/// Environment variable set by the runner to receive exact timings and answers,
+/// see [`EXEC_TIME_MARKER`] and [`ANSWER_MARKER`].
+pub const EXEC_TIME_ENV: &str = "AOC_EXEC_TIME";
+
 #[macro_export]
 macro_rules! solve {
     ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
-        }
+        use advent_of_code::{ANSI_BOLD, ANSI_RESET};
 
         println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        let input = $input.to_owned();
+        advent_of_code::print_part_result(
+            $part,
+            advent_of_code::run_timed(move || $solver(&input)),
+        );
+        if $part == 2 {
+            advent_of_code::exit_if_timed_out();
+        }
     }};
 }
 
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
+/// Like [`solve!`], but for days with a separate parse step: `$parser` runs once on the input
+/// and its output is shared by both parts. Parse time is reported on its own.
+///
+/// `$parser` returns a `Result`, a malformed input is reported with its error instead of a
+/// panic, see [`helpers::parse::ParseError`].
+#[macro_export]
+macro_rules! solve_parsed {
+    ($parser:ident, $part_one:ident, $part_two:ident, $input:expr) => {{
+        use advent_of_code::{ANSI_BOLD, ANSI_RESET};
+        use std::sync::Arc;
+
+        println!("🎄 {}Parse{} 🎄", ANSI_BOLD, ANSI_RESET);
+        let input = $input.to_owned();
+        let parsed = match advent_of_code::run_timed(move || $parser(&input)) {
+            Ok((Ok(parsed), elapsed)) => {
+                advent_of_code::print_result(Ok((Some("done"), elapsed)));
+                Arc::new(parsed)
+            }
+            Ok((Err(e), _)) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+            Err(limit) => {
+                advent_of_code::print_result::<&str>(Err(limit));
+                advent_of_code::exit_if_timed_out();
+                return;
+            }
+        };
 
-    let filepath = cwd.join("src").join(folder).join(format!("{day:02}.txt"));
+        println!("🎄 {}Part 1{} 🎄", ANSI_BOLD, ANSI_RESET);
+        let shared = Arc::clone(&parsed);
+        advent_of_code::print_part_result(1, advent_of_code::run_timed(move || $part_one(&shared)));
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+        println!("🎄 {}Part 2{} 🎄", ANSI_BOLD, ANSI_RESET);
+        let shared = Arc::clone(&parsed);
+        advent_of_code::print_part_result(2, advent_of_code::run_timed(move || $part_two(&shared)));
+        advent_of_code::exit_if_timed_out();
+    }};
 }
 
-fn parse_time(val: &str, postfix: &str) -> f64 {
-    val.split(postfix).next().unwrap().parse().unwrap()
+/// Stack size for solutions running on their own thread, the default main thread stack on
+/// Linux. Spawned threads otherwise only get 2 MiB.
+const SOLUTION_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Runs `func` and measures it. If a limit is set via [`TIMEOUT_ENV`], `func` runs on its own
+/// thread and `Err(limit)` is returned once the limit passes. The thread keeps running until the
+/// process ends, so later parts are measured next to it.
+pub fn run_timed<T: Send + 'static>(
+    func: impl FnOnce() -> T + Send + 'static,
+) -> Result<(T, Duration), Duration> {
+    let measure = move || {
+        let timer = Instant::now();
+        let result = func();
+        (result, timer.elapsed())
+    };
+
+    // without a limit, run on the main thread.
+    let Some(limit) = part_timeout() else {
+        return Ok(measure());
+    };
+
+    let (tx, rx) = mpsc::channel();
+    thread::Builder::new()
+        .stack_size(SOLUTION_STACK_SIZE)
+        .spawn(move || {
+            let _ = tx.send(measure());
+        })
+        .expect("failed to spawn the solution thread");
+
+    match rx.recv_timeout(limit) {
+        Ok(res) => Ok(res),
+        Err(RecvTimeoutError::Timeout) => Err(limit),
+        // `func` panicked, its message has already been printed.
+        Err(RecvTimeoutError::Disconnected) => process::exit(101),
+    }
 }
 
-pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
+pub fn print_result<T: Display>(result: Result<(Option<T>, Duration), Duration>) {
+    match result {
+        Ok((Some(result), elapsed)) => {
+            println!("{result} {ANSI_ITALIC}(elapsed: {elapsed:.2?}){ANSI_RESET}");
+            if env::var_os(EXEC_TIME_ENV).is_some() {
+                println!("{EXEC_TIME_MARKER}{}", elapsed.as_nanos());
             }
         }
-    })
+        Ok((None, _)) => {
+            println!("not solved.")
+        }
+        Err(limit) => {
+            println!("{ANSI_ITALIC}timed out after {limit:.2?}{ANSI_RESET}");
+            TIMED_OUT.store(true, Ordering::Relaxed);
+        }
+    }
 }
 
-/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-#[cfg(test)]
-macro_rules! assert_approx_eq {
-    ($a:expr, $b:expr) => {{
-        let (a, b) = (&$a, &$b);
-        assert!(
-            (*a - *b).abs() < 1.0e-6,
-            "{} is not approximately equal to {}",
-            *a,
-            *b
-        );
-    }};
+/// Set once a part timed out, so the remaining parts still run before the process fails.
+static TIMED_OUT: AtomicBool = AtomicBool::new(false);
+
+/// Ends the process with a failure if a part timed out. Called by [`solve!`] and
+/// [`solve_parsed!`] after the last part, so every part is reported first.
+pub fn exit_if_timed_out() {
+    if TIMED_OUT.load(Ordering::Relaxed) {
+        process::exit(1);
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Like [`print_result`], but also reports the answer of `part` via [`ANSWER_MARKER`] when
+/// [`EXEC_TIME_ENV`] is set.
+pub fn print_part_result<T: Display>(part: u8, result: Result<(Option<T>, Duration), Duration>) {
+    if let Ok((Some(answer), _)) = &result {
+        if env::var_os(EXEC_TIME_ENV).is_some() {
+            println!("{ANSWER_MARKER}{part}={answer}");
+        }
+    }
+    print_result(result);
+}
 
-    #[test]
-    fn test_parse_exec_time() {
-        assert_approx_eq!(
-            parse_exec_time(&format!(
-                "🎄 Part 1 🎄\n0 (elapsed: 74.13ns){}\n🎄 Part 2 🎄\n0 (elapsed: 50.00ns){}",
-                ANSI_RESET, ANSI_RESET
-            )),
-            0_f64
-        );
+pub fn read_file(folder: &str, day: u8) -> String {
+    let cwd = env::current_dir().unwrap();
 
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 755µs)\n🎄 Part 2 🎄\n0 (elapsed: 700µs)"),
-            1.455_f64
-        );
+    let filepath = if folder == "inputs" {
+        cwd.join(aoc_cli::get_input_path(day))
+    } else {
+        cwd.join("src").join(folder).join(format!("{day:02}.txt"))
+    };
 
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 70µs)\n🎄 Part 2 🎄\n0 (elapsed: 1.45ms)"),
-            1.52_f64
+    if folder == "inputs" && provenance::status(day) == provenance::Status::Modified {
+        eprintln!(
+            "warning: input for day {day:02} does not match the hash recorded in \"{}\", it was modified since it was downloaded.",
+            provenance::get_metadata_path(day)
         );
+    }
 
-        assert_approx_eq!(
-            parse_exec_time(
-                "🎄 Part 1 🎄\n0 (elapsed: 10.3s)\n🎄 Part 2 🎄\n0 (elapsed: 100.50ms)"
-            ),
-            10400.50_f64
-        );
+    let f = fs::read_to_string(&filepath);
+
+    // fall back to the encrypted store if there is no plain input.
+    let has_input = f.as_ref().is_ok_and(|input| !input.is_empty());
+    if folder == "inputs" && !has_input && Path::new(&encryption::get_encrypted_path(day)).exists()
+    {
+        return match encryption::read_input(day) {
+            Ok(input) => input,
+            // without any key, e.g. in CI without the secret, the day is skipped.
+            Err(e @ encryption::EncryptionError::NoKey) => {
+                eprintln!("input unavailable, skipping day {day:02}: {e}");
+                process::exit(0);
+            }
+            Err(e) => {
+                eprintln!(
+                    "could not read encrypted input \"{}\": {e}",
+                    encryption::get_encrypted_path(day)
+                );
+                process::exit(1);
+            }
+        };
+    }
+
+    f.expect("could not open input file")
+}
+
+/// Reads the per-part time limit from [`TIMEOUT_ENV`], if one is set.
+pub fn part_timeout() -> Option<Duration> {
+    let val = env::var(TIMEOUT_ENV).ok()?;
+    match parse_duration(&val) {
+        Ok(limit) => Some(limit),
+        Err(e) => {
+            eprintln!("ignoring {TIMEOUT_ENV}: {e}");
+            None
+        }
     }
 }
 
-pub mod aoc_cli {
-    use std::{
-        fmt::Display,
-        fs::create_dir_all,
-        process::{Command, Output, Stdio},
+/// Parses a human duration such as `500ms`, `1.5s` or `2m`. A bare number is read as seconds.
+pub fn parse_duration(val: &str) -> Result<Duration, String> {
+    let val = val.trim();
+    let split = val
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(val.len());
+    let (amount, unit) = val.split_at(split);
+
+    let amount: f64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration \"{val}\""))?;
+
+    let secs = match unit.trim() {
+        "ns" => amount / 1e9,
+        "us" | "µs" => amount / 1e6,
+        "ms" => amount / 1e3,
+        "" | "s" => amount,
+        "m" => amount * 60.0,
+        "h" => amount * 3600.0,
+        unit => return Err(format!("unknown duration unit \"{unit}\" in \"{val}\"")),
     };
 
-    pub enum AocCliError {
-        CommandNotFound,
-        CommandNotCallable,
-        BadExitStatus(Output),
-        IoError,
-    }
+    Duration::try_from_secs_f64(secs).map_err(|_| format!("duration \"{val}\" is too long"))
+}
 
-    impl Display for AocCliError {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            match self {
-                AocCliError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-                AocCliError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-                AocCliError::BadExitStatus(_) => {
-                    write!(f, "aoc-cli exited with a non-zero status.")
-                }
-                AocCliError::IoError => write!(f, "could not write output files to file system."),
-            }
-        }
-    }
+/// Prefix of the machine-readable timing lines a solution prints when [`EXEC_TIME_ENV`] is set.
+pub const EXEC_TIME_MARKER: &str = "@aoc elapsed_ns=";
+
+/// Sums the exact timings reported via [`EXEC_TIME_MARKER`] lines in a solution's output.
+pub fn parse_exec_time(output: &str) -> Duration {
+    output
+        .lines()
+        .filter_map(|l| l.strip_prefix(EXEC_TIME_MARKER))
+        .filter_map(|nanos| nanos.trim().parse::<u64>().ok())
+        .map(Duration::from_nanos)
+        .sum()
+}
 
-    pub fn check() -> Result<(), AocCliError> {
-        Command::new("aoc")
-            .arg("-V")
-            .output()
-            .map_err(|_| AocCliError::CommandNotFound)?;
-        Ok(())
-    }
+/// Prefix of the machine-readable answer lines, followed by the part, e.g. `@aoc answer_1=24000`.
+pub const ANSWER_MARKER: &str = "@aoc answer_";
 
-    pub fn read(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        // TODO: output local puzzle if present.
-        let args = build_args("read", &[], day, year);
-        call_aoc_cli(&args)
-    }
+/// Reads the answer to `part` reported via [`ANSWER_MARKER`] from a solution's output.
+pub fn parse_answer(output: &str, part: u8) -> Option<String> {
+    let prefix = format!("{ANSWER_MARKER}{part}=");
+    output
+        .lines()
+        .find_map(|l| l.strip_prefix(&prefix))
+        .map(|answer| answer.to_string())
+}
+
+/// Removes the [`EXEC_TIME_MARKER`] and [`ANSWER_MARKER`] lines from a solution's output,
+/// leaving what a user should see.
+pub fn strip_markers(output: &str) -> String {
+    output
+        .lines()
+        .filter(|l| !l.starts_with(EXEC_TIME_MARKER) && !l.starts_with(ANSWER_MARKER))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    pub fn download(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        let input_path = get_input_path(day);
-
-        let puzzle_path = get_puzzle_path(day);
-        create_dir_all("src/puzzles").map_err(|_| AocCliError::IoError)?;
-
-        let args = build_args(
-            "download",
-            &[
-                "--overwrite".into(),
-                "--input-file".into(),
-                input_path.to_string(),
-                "--puzzle-file".into(),
-                puzzle_path.to_string(),
-            ],
-            day,
-            year,
+    #[test]
+    fn test_parse_exec_time() {
+        assert_eq!(
+            parse_exec_time(&format!(
+                "🎄 Part 1 🎄\n0 {ANSI_ITALIC}(elapsed: 74.00ns){ANSI_RESET}\n{EXEC_TIME_MARKER}74\n🎄 Part 2 🎄\n0 {ANSI_ITALIC}(elapsed: 50.00ns){ANSI_RESET}\n{EXEC_TIME_MARKER}50"
+            )),
+            Duration::from_nanos(124)
         );
 
-        let output = call_aoc_cli(&args)?;
+        assert_eq!(
+            parse_exec_time(&format!(
+                "🎄 Part 1 🎄\n0 (elapsed: 70.00µs)\n{EXEC_TIME_MARKER}70000\n🎄 Part 2 🎄\n0 (elapsed: 1.45ms)\n{EXEC_TIME_MARKER}1450123"
+            )),
+            Duration::from_nanos(1_520_123)
+        );
 
-        if output.status.success() {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-            Ok(output)
-        } else {
-            Err(AocCliError::BadExitStatus(output))
-        }
+        assert_eq!(
+            parse_exec_time(&format!(
+                "{EXEC_TIME_MARKER}10300000000\n{EXEC_TIME_MARKER}100500000"
+            )),
+            Duration::from_nanos(10_400_500_000)
+        );
     }
 
-    fn get_input_path(day: u8) -> String {
-        let day_padded = format!("{day:02}");
-        format!("src/inputs/{day_padded}.txt")
+    #[test]
+    fn test_parse_exec_time_ignores_human_output() {
+        assert_eq!(
+            parse_exec_time(&format!(
+                "🎄 Part 1 🎄\n0 (elapsed: 1.50ms)\n{EXEC_TIME_MARKER}1500000\n🎄 Part 2 🎄\n{ANSI_ITALIC}timed out after 2.00s{ANSI_RESET}"
+            )),
+            Duration::from_micros(1500)
+        );
+        assert_eq!(
+            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 755µs)"),
+            Duration::ZERO
+        );
     }
 
-    fn get_puzzle_path(day: u8) -> String {
-        let day_padded = format!("{day:02}");
-        format!("src/puzzles/{day_padded}.md")
+    #[test]
+    fn test_strip_markers() {
+        assert_eq!(
+            strip_markers(&format!(
+                "🎄 Part 1 🎄\n{ANSWER_MARKER}1=0\n0 (elapsed: 74.00ns)\n{EXEC_TIME_MARKER}74\n"
+            )),
+            "🎄 Part 1 🎄\n0 (elapsed: 74.00ns)"
+        );
     }
 
-    fn build_args(command: &str, args: &[String], day: u8, year: Option<u16>) -> Vec<String> {
-        let mut cmd_args = args.to_vec();
-
-        if let Some(year) = year {
-            cmd_args.push("--year".into());
-            cmd_args.push(year.to_string());
-        }
-
-        cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
-
-        cmd_args
+    #[test]
+    fn test_parse_answer() {
+        let output = format!(
+            "🎄 Part 1 🎄\n{ANSWER_MARKER}1=CMZ\nCMZ (elapsed: 74.00ns)\n🎄 Part 2 🎄\nnot solved."
+        );
+        assert_eq!(parse_answer(&output, 1), Some("CMZ".into()));
+        assert_eq!(parse_answer(&output, 2), None);
     }
 
-    fn call_aoc_cli(args: &[String]) -> Result<Output, AocCliError> {
-        if cfg!(debug_assertions) {
-            println!("Calling >aoc with: {}", args.join(" "));
-        }
-
-        Command::new("aoc")
-            .args(args)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()
-            .map_err(|_| AocCliError::CommandNotCallable)
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("3m"), Ok(Duration::from_secs(180)));
+        assert_eq!(parse_duration("250µs"), Ok(Duration::from_micros(250)));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("10 parsecs").is_err());
+        assert!(parse_duration("100000000000000000000h").is_err());
     }
 }
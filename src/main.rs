@@ -2,44 +2,208 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use advent_of_code::aoc_cli::get_profile_puzzle_path;
+use advent_of_code::profile::{self, PROFILE_ENV};
+use advent_of_code::{
+    parse_answer, parse_duration, puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, EXEC_TIME_ENV,
+    TIMEOUT_ENV,
+};
+use std::fs;
+use std::io::Read;
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+struct Args {
+    /// limit for every single part, passed on to the solutions.
+    timeout: Option<Duration>,
+    /// limit for the whole run, enforced by killing the running solution.
+    total_timeout: Option<Duration>,
+    /// run every day on every input set and compare with the answers recorded for it.
+    profiles: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+        total_timeout: args.opt_value_from_fn("--total-timeout", parse_duration)?,
+        profiles: args.contains("--profiles"),
+    })
+}
+
+enum RunResult {
+    Finished(String),
+    TimedOut(Duration),
+}
+
+fn run_day(day: &str, args: &Args, deadline: Option<Instant>, profile: Option<&str>) -> RunResult {
+    let mut cmd_args = vec!["run", "--bin", day];
+    if cfg!(not(debug_assertions)) {
+        cmd_args.push("--release");
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .env(EXEC_TIME_ENV, "1");
+
+    match profile {
+        Some(profile) => cmd.env(PROFILE_ENV, profile),
+        None => cmd.env_remove(PROFILE_ENV),
+    };
+
+    if let Some(timeout) = args.timeout {
+        cmd.env(TIMEOUT_ENV, format!("{}ns", timeout.as_nanos()));
+    }
+
+    let mut child = cmd.spawn().unwrap();
+
+    // read on a separate thread so a chatty solution cannot block on a full pipe.
+    let mut stdout = child.stdout.take().unwrap();
+    let reader = thread::spawn(move || {
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).unwrap();
+        output
+    });
+
+    let started = Instant::now();
+    loop {
+        if child.try_wait().unwrap().is_some() {
+            return RunResult::Finished(reader.join().unwrap());
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            child.kill().unwrap();
+            child.wait().unwrap();
+            return RunResult::TimedOut(started.elapsed());
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Compares the answers in `output` with those recorded in the puzzle of `profile`.
+/// Returns whether no answer differs.
+fn check_answers(day: u8, profile: Option<&str>, output: &str) -> bool {
+    let recorded = fs::read_to_string(get_profile_puzzle_path(day, profile))
+        .map(|markdown| puzzle::answers(&markdown))
+        .unwrap_or_default();
+
+    let mut matches = true;
+    let parts: Vec<String> = (1..=2)
+        .map(|part| {
+            let answer = parse_answer(output, part);
+            let expected = recorded.get(usize::from(part) - 1);
+            match (answer, expected) {
+                (None, _) => format!("part {part} not solved"),
+                (Some(answer), None) => format!("part {part} ? {answer} (no recorded answer)"),
+                (Some(answer), Some(expected)) if &answer == expected => {
+                    format!("part {part} ✓ {answer}")
+                }
+                (Some(answer), Some(expected)) => {
+                    matches = false;
+                    format!("part {part} ✗ {answer} (expected {expected})")
+                }
+            }
+        })
+        .collect();
+
+    let name = profile.unwrap_or(profile::DEFAULT_PROFILE);
+    println!("🎄 {ANSI_BOLD}{name}{ANSI_RESET}: {}", parts.join(", "));
+    matches
+}
+
+/// Runs `day` on every input set, see [`profile::input_sets`].
+fn run_day_profiles(day: u8, args: &Args, deadline: Option<Instant>) -> (Duration, bool) {
+    let sets = profile::input_sets(day);
+    if sets.is_empty() {
+        println!("No inputs.");
+        return (Duration::ZERO, true);
+    }
+
+    let mut total = Duration::ZERO;
+    let mut matches = true;
+    for profile in sets {
+        let name = profile.as_deref().unwrap_or(profile::DEFAULT_PROFILE);
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            println!("🎄 {ANSI_BOLD}{name}{ANSI_RESET}: {ANSI_ITALIC}skipped: total time limit reached{ANSI_RESET}");
+            continue;
+        }
+
+        match run_day(&format!("{day:02}"), args, deadline, profile.as_deref()) {
+            RunResult::Finished(output) => {
+                matches &= check_answers(day, profile.as_deref(), &output);
+                total += advent_of_code::parse_exec_time(&output);
+            }
+            RunResult::TimedOut(elapsed) => {
+                println!("🎄 {ANSI_BOLD}{name}{ANSI_RESET}: {ANSI_ITALIC}timed out after {elapsed:.2?}{ANSI_RESET}");
+            }
+        }
+    }
+    (total, matches)
+}
 
 fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{day:02}");
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    let deadline = args.total_timeout.map(|limit| Instant::now() + limit);
+    let current_profile = profile::current();
+    let mut all_match = true;
+
+    let total: Duration = (1..=25)
+        .map(|day_num| {
+            let day = format!("{day_num:02}");
+
+            let heading = format!("| {} |", puzzle::heading(day_num));
+            let rule = "-".repeat(heading.chars().count());
+            println!("{rule}");
+            println!("{ANSI_BOLD}{heading}{ANSI_RESET}");
+            println!("{rule}");
 
-            let mut args = vec!["run", "--bin", &day];
-            if cfg!(not(debug_assertions)) {
-                args.push("--release");
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                println!("{ANSI_ITALIC}skipped: total time limit reached{ANSI_RESET}");
+                return Duration::ZERO;
             }
 
-            let cmd = Command::new("cargo").args(&args).output().unwrap();
+            if args.profiles {
+                let (total, matches) = run_day_profiles(day_num, &args, deadline);
+                all_match &= matches;
+                return total;
+            }
 
-            println!("----------");
-            println!("{ANSI_BOLD}| Day {day} |{ANSI_RESET}");
-            println!("----------");
+            let output = match run_day(&day, &args, deadline, current_profile.as_deref()) {
+                RunResult::Finished(output) => output,
+                RunResult::TimedOut(elapsed) => {
+                    println!("{ANSI_ITALIC}timed out after {elapsed:.2?}{ANSI_RESET}");
+                    return Duration::ZERO;
+                }
+            };
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
             let is_empty = output.is_empty();
 
             println!(
                 "{}",
                 if is_empty {
-                    "Not solved."
+                    "Not solved.".into()
                 } else {
-                    output.trim()
+                    advent_of_code::strip_markers(output.trim())
                 }
             );
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
-            }
+            advent_of_code::parse_exec_time(&output)
         })
         .sum();
 
-    println!("{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total:.2}ms{ANSI_RESET}");
+    println!("{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total:.2?}{ANSI_RESET}");
+
+    if !all_match {
+        eprintln!("Some answers differ from the recorded ones.");
+        process::exit(1);
+    }
 }
@@ -0,0 +1,124 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::aoc_cli;
+
+/// Environment variable selecting the input set to use, e.g. `AOC_PROFILE=alice`.
+/// Without it, inputs live directly in `src/inputs`.
+pub const PROFILE_ENV: &str = "AOC_PROFILE";
+
+/// Name shown for the input set directly in `src/inputs`.
+pub const DEFAULT_PROFILE: &str = "default";
+
+pub const INPUTS_DIR: &str = "src/inputs";
+pub const PUZZLES_DIR: &str = "src/puzzles";
+
+/// The profile selected via [`PROFILE_ENV`], if any. An invalid name is an error that ends the
+/// process, like a bad `--profile` argument, as it would end up in file paths.
+pub fn current() -> Option<String> {
+    let profile = env::var(PROFILE_ENV)
+        .ok()
+        .filter(|profile| !profile.trim().is_empty())?;
+
+    match parse(&profile) {
+        Ok(profile) => Some(profile),
+        Err(e) => {
+            eprintln!("Invalid {PROFILE_ENV}: {e}.");
+            process::exit(1);
+        }
+    }
+}
+
+/// Profile names become directory and file names, so they are restricted to a safe set of characters.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Parses a profile name given on the command line.
+pub fn parse(val: &str) -> Result<String, String> {
+    if is_valid_name(val) {
+        Ok(val.into())
+    } else {
+        Err(format!(
+            "\"{val}\" is not a valid profile name, use letters, digits, '-' and '_'"
+        ))
+    }
+}
+
+/// `base` for the default input set, `base/<profile>` for a named one.
+pub fn dir(base: &str, profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => format!("{base}/{profile}"),
+        None => base.into(),
+    }
+}
+
+/// Names of all profiles, i.e. the subdirectories of [`INPUTS_DIR`], sorted.
+pub fn list() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(INPUTS_DIR) else {
+        return vec![];
+    };
+
+    let mut profiles: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_valid_name(name))
+        .collect();
+
+    profiles.sort();
+    profiles
+}
+
+/// Whether `profile` has a plain or encrypted input for `day`.
+pub fn has_input(day: u8, profile: Option<&str>) -> bool {
+    let path = aoc_cli::get_profile_input_path(day, profile);
+    fs::metadata(&path).is_ok_and(|m| m.len() > 0) || Path::new(&format!("{path}.enc")).exists()
+}
+
+/// Input sets with an input for `day`: the default one, followed by every profile.
+pub fn input_sets(day: u8) -> Vec<Option<String>> {
+    let profiles = list().into_iter().map(Some);
+    [None]
+        .into_iter()
+        .chain(profiles)
+        .filter(|profile| has_input(day, profile.as_deref()))
+        .collect()
+}
+
+/// The session cookie file of `profile`: `~/.adventofcode.<profile>.session`.
+pub fn session_file(profile: &str) -> Option<PathBuf> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(format!(".adventofcode.{profile}.session")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(is_valid_name("alice"));
+        assert!(is_valid_name("work-account_2"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("../alice"));
+        assert!(!is_valid_name("bob smith"));
+    }
+
+    #[test]
+    fn test_dir() {
+        assert_eq!(dir(INPUTS_DIR, None), "src/inputs");
+        assert_eq!(dir(INPUTS_DIR, Some("alice")), "src/inputs/alice");
+        assert_eq!(dir(PUZZLES_DIR, Some("alice")), "src/puzzles/alice");
+    }
+}
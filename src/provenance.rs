@@ -0,0 +1,167 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use sha2::{Digest, Sha256};
+use std::{env, fmt::Display, fs, io, time::SystemTime};
+
+use crate::{calendar, profile};
+
+/// Environment variable naming the account inputs are downloaded with.
+pub const ACCOUNT_ENV: &str = "AOC_ACCOUNT";
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+/// Where an input file came from, stored next to it as `src/inputs/NN.meta`
+/// (`src/inputs/<profile>/NN.meta` for a profile).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Metadata {
+    pub year: u16,
+    pub day: u8,
+    /// ISO 8601 UTC timestamp of the download.
+    pub downloaded_at: String,
+    /// Hex encoded SHA-256 of the input file.
+    pub sha256: String,
+    pub account: String,
+}
+
+impl Metadata {
+    pub fn new(year: u16, day: u8, input: &[u8], account: &str) -> Self {
+        Metadata {
+            year,
+            day,
+            downloaded_at: calendar::format_utc(SystemTime::now()),
+            sha256: sha256_hex(input),
+            account: account.into(),
+        }
+    }
+
+    /// Parses the `key = value` lines written by [`Metadata`]'s `Display` impl.
+    pub fn parse(val: &str) -> Option<Self> {
+        let field = |key: &str| {
+            val.lines().find_map(|line| {
+                let (k, v) = line.split_once('=')?;
+                (k.trim() == key).then(|| v.trim().to_string())
+            })
+        };
+
+        Some(Metadata {
+            year: field("year")?.parse().ok()?,
+            day: field("day")?.parse().ok()?,
+            downloaded_at: field("downloaded_at")?,
+            sha256: field("sha256")?,
+            account: field("account")?,
+        })
+    }
+}
+
+impl Display for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "year = {}", self.year)?;
+        writeln!(f, "day = {}", self.day)?;
+        writeln!(f, "downloaded_at = {}", self.downloaded_at)?;
+        writeln!(f, "sha256 = {}", self.sha256)?;
+        writeln!(f, "account = {}", self.account)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// No input file, or an empty one as created by `scaffold`.
+    Missing,
+    /// The input has no metadata, so its origin is unknown.
+    Untracked,
+    /// The input matches the hash recorded when it was downloaded.
+    Unmodified,
+    /// The input changed since it was downloaded.
+    Modified,
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// The account label to record: [`ACCOUNT_ENV`] if set, otherwise the current profile.
+pub fn account() -> String {
+    env::var(ACCOUNT_ENV)
+        .ok()
+        .or_else(profile::current)
+        .unwrap_or_else(|| DEFAULT_ACCOUNT.into())
+}
+
+pub fn get_metadata_path(day: u8) -> String {
+    let dir = profile::dir(profile::INPUTS_DIR, profile::current().as_deref());
+    format!("{dir}/{day:02}.meta")
+}
+
+pub fn read(day: u8) -> Option<Metadata> {
+    Metadata::parse(&fs::read_to_string(get_metadata_path(day)).ok()?)
+}
+
+pub fn write(metadata: &Metadata) -> io::Result<()> {
+    fs::write(get_metadata_path(metadata.day), metadata.to_string())
+}
+
+/// Compares the input of `day` to its metadata.
+pub fn status(day: u8) -> Status {
+    let input = match fs::read(crate::aoc_cli::get_input_path(day)) {
+        Ok(input) if !input.is_empty() => input,
+        _ => return Status::Missing,
+    };
+    status_of(&input, read(day).as_ref())
+}
+
+fn status_of(input: &[u8], metadata: Option<&Metadata>) -> Status {
+    match metadata {
+        None => Status::Untracked,
+        Some(metadata) if metadata.sha256 == sha256_hex(input) => Status::Unmodified,
+        Some(_) => Status::Modified,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(input: &[u8]) -> Metadata {
+        Metadata {
+            year: 2022,
+            day: 6,
+            downloaded_at: "2022-12-06T05:00:01Z".into(),
+            sha256: sha256_hex(input),
+            account: "github".into(),
+        }
+    }
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_metadata_roundtrip() {
+        let metadata = metadata(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        assert_eq!(Metadata::parse(&metadata.to_string()), Some(metadata));
+        assert_eq!(Metadata::parse("year = 2022\nday = 6"), None);
+        assert_eq!(Metadata::parse("year = next\nday = 6"), None);
+    }
+
+    #[test]
+    fn test_status_of() {
+        let metadata = metadata(b"1000\n2000\n");
+        assert_eq!(status_of(b"1000\n2000\n", None), Status::Untracked);
+        assert_eq!(
+            status_of(b"1000\n2000\n", Some(&metadata)),
+            Status::Unmodified
+        );
+        assert_eq!(
+            status_of(b"1000\n2001\n", Some(&metadata)),
+            Status::Modified
+        );
+    }
+}
@@ -0,0 +1,357 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use regex::Regex;
+use std::fs;
+
+use crate::aoc_cli::{get_profile_puzzle_path, get_puzzle_path};
+use crate::calendar::{FIRST_DAY, LAST_DAY};
+
+/// What a downloaded puzzle description tells about a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleInfo {
+    pub day: u8,
+    pub title: Option<String>,
+    /// Part two is only included in the description once part one is solved.
+    pub has_part_two: bool,
+    /// See [`answers`].
+    pub answers: Vec<String>,
+}
+
+impl PuzzleInfo {
+    pub fn parse(day: u8, markdown: &str) -> Self {
+        PuzzleInfo {
+            day,
+            title: title(markdown),
+            has_part_two: has_part_two(markdown),
+            answers: answers(markdown),
+        }
+    }
+
+    /// `Day 06: Tuning Trouble`, or `Day 06` if the title is unknown.
+    pub fn heading(&self) -> String {
+        format_heading(self.day, self.title.as_deref())
+    }
+}
+
+/// Reads the puzzle of `day` as seen by the current profile, if it was downloaded.
+pub fn info(day: u8) -> Option<PuzzleInfo> {
+    let markdown = fs::read_to_string(get_puzzle_path(day)).ok()?;
+    Some(PuzzleInfo::parse(day, &markdown))
+}
+
+/// Every downloaded puzzle of the current profile, by day.
+pub fn index() -> Vec<PuzzleInfo> {
+    (FIRST_DAY..=LAST_DAY).filter_map(info).collect()
+}
+
+/// Like [`PuzzleInfo::heading`], for days that may not be downloaded. Titles are the same for
+/// every account, so the default puzzle is used if the current profile has none.
+pub fn heading(day: u8) -> String {
+    let title = info(day).and_then(|info| info.title).or_else(|| {
+        let markdown = fs::read_to_string(get_profile_puzzle_path(day, None)).ok()?;
+        title(&markdown)
+    });
+    format_heading(day, title.as_deref())
+}
+
+fn format_heading(day: u8, title: Option<&str>) -> String {
+    match title {
+        Some(title) => format!("Day {day:02}: {title}"),
+        None => format!("Day {day:02}"),
+    }
+}
+
+/// Reads the title from the header of a puzzle description, e.g. `--- Day 6: Tuning Trouble ---`.
+pub fn title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let header = line.trim().trim_start_matches('\\');
+        let header = header.strip_prefix("--- Day ")?.strip_suffix(" ---")?;
+        let (_, title) = header.split_once(": ")?;
+        Some(title.trim().to_string())
+    })
+}
+
+pub fn has_part_two(markdown: &str) -> bool {
+    markdown
+        .lines()
+        .any(|line| line.trim().trim_start_matches('\\') == "--- Part Two ---")
+}
+
+/// Answers accepted by the site, in order of the parts. A downloaded puzzle records them as
+/// `` Your puzzle answer was `69528`. `` below each solved part.
+pub fn answers(markdown: &str) -> Vec<String> {
+    markdown
+        .lines()
+        .filter_map(|line| {
+            let answer = line.trim().strip_prefix("Your puzzle answer was `")?;
+            let (answer, _) = answer.split_once('`')?;
+            Some(answer.to_string())
+        })
+        .collect()
+}
+
+/// Extracts the example input from a puzzle description: the first code block following a
+/// paragraph that mentions an example, or the first code block if no paragraph does.
+pub fn example(markdown: &str) -> Option<String> {
+    let blocks = code_blocks(markdown);
+    let (_, block) = blocks
+        .iter()
+        .find(|(intro, _)| intro.to_lowercase().contains("example"))
+        .or_else(|| blocks.first())?;
+
+    let block = block.trim_end_matches('\n');
+    if block.is_empty() {
+        None
+    } else {
+        Some(format!("{block}\n"))
+    }
+}
+
+/// Converts a puzzle page to the markdown aoc-cli writes: the puzzle descriptions along with the
+/// answers given so far. Other parts of the page, such as the answer form, are left out.
+pub fn from_html(html: &str) -> String {
+    let sections =
+        Regex::new(r"(?s)<article[^>]*>(.*?)</article>|<p>(Your puzzle answer was.*?)</p>")
+            .unwrap();
+
+    let mut markdown = String::new();
+    for section in sections.captures_iter(html) {
+        match (section.get(1), section.get(2)) {
+            (Some(article), _) => markdown.push_str(&html_to_markdown(article.as_str())),
+            (_, Some(answer)) => {
+                markdown.push_str(&html_to_markdown(answer.as_str()));
+                markdown.push_str("\n\n");
+            }
+            _ => {}
+        }
+    }
+
+    if markdown.is_empty() {
+        markdown = html_to_markdown(html);
+    }
+
+    format!("{}\n", markdown.trim_end())
+}
+
+/// Converts the small subset of HTML used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let tokens = Regex::new(r#"(?s)<(/?)(\w+)([^>]*)>|([^<]+)"#).unwrap();
+    let href = Regex::new(r#"href="([^"]*)""#).unwrap();
+
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut links = vec![];
+
+    for token in tokens.captures_iter(html) {
+        if let Some(text) = token.get(4) {
+            markdown.push_str(&decode_entities(text.as_str()));
+            continue;
+        }
+
+        let closing = &token[1] == "/";
+        match (&token[2], closing) {
+            ("h2", false) => markdown.push('\\'),
+            ("h2", true) => markdown.push_str("\n----------\n\n"),
+            ("p", true) | ("ul", true) => markdown.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => markdown.push('`'),
+            ("em", _) if !in_pre => markdown.push('*'),
+            ("li", false) => markdown.push_str("* "),
+            ("li", true) => markdown.push('\n'),
+            ("a", false) => {
+                links.push(href.captures(&token[3]).map(|c| c[1].to_string()));
+                markdown.push('[');
+            }
+            ("a", true) => match links.pop().flatten() {
+                Some(href) => markdown.push_str(&format!("]({href})")),
+                None => markdown.push(']'),
+            },
+            _ => {}
+        }
+    }
+
+    markdown
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Fenced code blocks in `markdown`, along with the paragraph right before each of them.
+fn code_blocks(markdown: &str) -> Vec<(String, String)> {
+    let mut blocks = vec![];
+    let mut paragraph = String::new();
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        let is_fence = line.trim_start().starts_with("```");
+        match (&mut block, is_fence) {
+            (None, true) => block = Some(String::new()),
+            (None, false) if line.trim().is_empty() => {}
+            (None, false) => paragraph = line.to_string(),
+            (Some(content), true) => {
+                blocks.push((paragraph.clone(), std::mem::take(content)));
+                block = None;
+            }
+            (Some(content), false) => {
+                content.push_str(line);
+                content.push('\n');
+            }
+        }
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "\\--- Day 6: Tuning Trouble ---
+----------
+
+The preparations are finally complete:
+
+```
+not the example
+```
+
+For example, suppose you receive the following datastream buffer:
+
+```
+mjqjpqmgbljsphdztnvjfqwrcgsmlb
+
+```
+
+After the first three characters (`mjq`) have been received.
+";
+
+    #[test]
+    fn test_title() {
+        assert_eq!(title(PUZZLE), Some("Tuning Trouble".into()));
+        assert_eq!(
+            title("--- Day 25: Full of Hot Air ---"),
+            Some("Full of Hot Air".into())
+        );
+        assert_eq!(title("no title here"), None);
+    }
+
+    #[test]
+    fn test_puzzle_info() {
+        let info = PuzzleInfo::parse(6, PUZZLE);
+        assert_eq!(info.title, Some("Tuning Trouble".into()));
+        assert!(!info.has_part_two);
+        assert!(info.answers.is_empty());
+        assert_eq!(info.heading(), "Day 06: Tuning Trouble");
+        assert_eq!(format_heading(7, None), "Day 07");
+    }
+
+    #[test]
+    fn test_puzzle_info_from_puzzles() {
+        let puzzle = std::fs::read_to_string("src/puzzles/01.md").unwrap();
+        assert_eq!(
+            PuzzleInfo::parse(1, &puzzle),
+            PuzzleInfo {
+                day: 1,
+                title: Some("Calorie Counting".into()),
+                has_part_two: true,
+                answers: vec!["69528".into(), "206152".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(answers(PUZZLE), Vec::<String>::new());
+        assert_eq!(
+            answers("Part one.\n\nYour puzzle answer was `69528`.\n\nPart two.\n\nYour puzzle answer was `206152`.\n"),
+            vec!["69528", "206152"]
+        );
+        assert_eq!(
+            answers("Your puzzle answer was `NTWZZWHFV`."),
+            vec!["NTWZZWHFV"]
+        );
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(
+            example(PUZZLE),
+            Some("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n".into())
+        );
+        assert_eq!(
+            example("```\n    [D]\n 1 2\n```"),
+            Some("    [D]\n 1 2\n".into())
+        );
+        assert_eq!(example("no code blocks"), None);
+        assert_eq!(example("For example:\n\n```\n\n```"), None);
+    }
+
+    const PUZZLE_HTML: &str = r#"<body><main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>Santa's reindeer typically eat regular reindeer food.</p>
+<p>For example, suppose the Elves finish writing their items' <em>Calories</em> into the following list:</p>
+<pre><code>1000
+2000
+
+<em>4000</em>
+</code></pre>
+<p>Find the Elf carrying the <a href="/2022/day/1#part2">most Calories</a> &amp; more.</p>
+</article>
+<p>Your puzzle answer was <code>69528</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>By the time you calculate the answer...</p>
+</article>
+<form method="post" action="1/answer"><input type="hidden" name="level" value="2"/><p>Answer: <input type="text" name="answer"/></p></form>
+</main></body>"#;
+
+    #[test]
+    fn test_from_html() {
+        let markdown = from_html(PUZZLE_HTML);
+        assert!(markdown.starts_with("\\--- Day 1: Calorie Counting ---\n----------\n\n"));
+        assert!(markdown.contains("items' *Calories* into"));
+        assert!(markdown.contains("[most Calories](/2022/day/1#part2) & more."));
+        assert!(!markdown.contains("Answer:"));
+
+        assert_eq!(
+            PuzzleInfo::parse(1, &markdown),
+            PuzzleInfo {
+                day: 1,
+                title: Some("Calorie Counting".into()),
+                has_part_two: true,
+                answers: vec!["69528".into()],
+            }
+        );
+        assert_eq!(example(&markdown), Some("1000\n2000\n\n4000\n".into()));
+    }
+
+    #[test]
+    fn test_from_html_response() {
+        assert_eq!(
+            from_html("<main>\n<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article>\n</main>"),
+            "That's the right answer!  You are one gold star closer.\n"
+        );
+    }
+
+    #[test]
+    fn test_example_from_puzzles() {
+        let puzzle = std::fs::read_to_string("src/puzzles/05.md").unwrap();
+        let expected = std::fs::read_to_string("src/examples/05.txt").unwrap();
+        assert_eq!(example(&puzzle), Some(expected));
+    }
+}
@@ -0,0 +1,394 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::aoc_cli::Failure;
+use crate::{calendar, profile, puzzle};
+
+/// Environment variable pointing requests at another site than adventofcode.com, e.g. a local
+/// stand-in for testing (`AOC_URL=http://localhost:8080`). aoc-cli cannot be redirected, so
+/// setting it switches to the built-in client.
+pub const URL_ENV: &str = "AOC_URL";
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the session cookie. Takes precedence over the session file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Environment variable with contact details, e.g. an email address or the URL of your
+/// repository, added to the user agent so the site's operators can reach you.
+pub const CONTACT_ENV: &str = "AOC_CONTACT";
+
+const USER_AGENT: &str = concat!("advent_of_code/", env!("CARGO_PKG_VERSION"));
+
+/// The user agent sent with requests, including the contact from [`CONTACT_ENV`] if set.
+fn user_agent() -> String {
+    match env::var(CONTACT_ENV).map(|contact| contact.trim().to_string()) {
+        Ok(contact) if !contact.is_empty() => format!("{USER_AGENT} ({contact})"),
+        _ => USER_AGENT.into(),
+    }
+}
+
+#[derive(Debug)]
+pub enum SiteError {
+    MissingSession(PathBuf),
+    Status {
+        status: u16,
+        failure: Failure,
+        body: String,
+    },
+    Transport(String),
+}
+
+impl SiteError {
+    pub fn failure(&self) -> Failure {
+        match self {
+            SiteError::MissingSession(_) => Failure::MissingSession,
+            SiteError::Status { failure, .. } => *failure,
+            SiteError::Transport(_) => Failure::Unknown,
+        }
+    }
+}
+
+impl Display for SiteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SiteError::MissingSession(path) => write!(
+                f,
+                "no session cookie found in {SESSION_ENV} or \"{}\".",
+                path.display()
+            ),
+            SiteError::Status {
+                status, failure, ..
+            } => match failure {
+                Failure::Unknown => write!(f, "request failed with status {status}."),
+                failure => write!(f, "request failed with status {status}: {failure}."),
+            },
+            SiteError::Transport(e) => write!(f, "request failed: {e}"),
+        }
+    }
+}
+
+impl Error for SiteError {}
+
+/// What the site answered to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    /// The answer is wrong, the site may say whether it is too high or too low.
+    Incorrect(Option<String>),
+    /// The previous answer was submitted too recently, along with the time left to wait.
+    RateLimited(Option<String>),
+    /// The part was solved already, or part one is still open.
+    WrongLevel,
+    Unknown(String),
+}
+
+impl SubmitOutcome {
+    /// Classifies the response to a submission, as returned by the site or printed by aoc-cli.
+    pub fn classify(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if response.contains("That's not the right answer") {
+            let hint = ["too high", "too low"]
+                .into_iter()
+                .find(|hint| response.contains(&format!("your answer is {hint}")));
+            SubmitOutcome::Incorrect(hint.map(String::from))
+        } else if response.contains("You gave an answer too recently") {
+            let wait = response
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.trim().to_string());
+            SubmitOutcome::RateLimited(wait)
+        } else if response.contains("You don't seem to be solving the right level") {
+            SubmitOutcome::WrongLevel
+        } else {
+            SubmitOutcome::Unknown(response.trim().to_string())
+        }
+    }
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmitOutcome::Incorrect(Some(hint)) => {
+                write!(f, "That's not the right answer, it is {hint}.")
+            }
+            SubmitOutcome::Incorrect(None) => write!(f, "That's not the right answer."),
+            SubmitOutcome::RateLimited(Some(wait)) => {
+                write!(
+                    f,
+                    "You gave an answer too recently, wait {wait} before trying again."
+                )
+            }
+            SubmitOutcome::RateLimited(None) => write!(f, "You gave an answer too recently."),
+            SubmitOutcome::WrongLevel => write!(
+                f,
+                "This part is not open, it was either solved already or part one is still open."
+            ),
+            SubmitOutcome::Unknown(response) => write!(f, "Unexpected response: {response}"),
+        }
+    }
+}
+
+/// The session cookie file of the current profile, `~/.adventofcode.session` without one.
+pub fn session_path() -> Option<PathBuf> {
+    match profile::current() {
+        Some(profile) => profile::session_file(&profile),
+        None => {
+            let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+            Some(Path::new(&home).join(".adventofcode.session"))
+        }
+    }
+}
+
+/// The session cookie set via [`SESSION_ENV`], if any.
+pub fn session_override() -> Option<String> {
+    env::var(SESSION_ENV)
+        .ok()
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/// Loads the session cookie from [`SESSION_ENV`], falling back to [`session_path`].
+pub fn load_session() -> Result<String, SiteError> {
+    if let Some(session) = session_override() {
+        return Ok(session);
+    }
+
+    let path = session_path().unwrap_or_else(|| PathBuf::from(".adventofcode.session"));
+    match fs::read_to_string(&path) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().into()),
+        _ => Err(SiteError::MissingSession(path)),
+    }
+}
+
+/// Cleans up a pasted session cookie, which may include the cookie name.
+pub fn parse_session(val: &str) -> Result<String, String> {
+    let session = val.trim();
+    let session = session.strip_prefix("session=").unwrap_or(session);
+    if session.is_empty() {
+        return Err("the session cookie is empty".into());
+    }
+    if session.contains(|c: char| c.is_whitespace() || c == ';') {
+        return Err("the session cookie must be a single value without spaces or `;`".into());
+    }
+    Ok(session.into())
+}
+
+/// Writes `session` to `path`, readable by the current user only.
+pub fn save_session(path: &Path, session: &str) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // the mode only applies to new files, so tighten an existing one first.
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    options
+        .open(path)?
+        .write_all(format!("{session}\n").as_bytes())
+}
+
+/// The site configured via [`URL_ENV`], if it is not the default one.
+pub fn url_override() -> Option<String> {
+    env::var(URL_ENV)
+        .ok()
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty() && url != DEFAULT_URL)
+}
+
+/// The site to talk to, see [`URL_ENV`].
+pub fn site_url() -> String {
+    url_override().unwrap_or_else(|| DEFAULT_URL.into())
+}
+
+/// A minimal client for the puzzle site, used where aoc-cli cannot be.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.into(),
+            agent: ureq::AgentBuilder::new().user_agent(&user_agent()).build(),
+        }
+    }
+
+    /// A client for the site in [`URL_ENV`], with the session from [`load_session`].
+    pub fn from_env() -> Result<Self, SiteError> {
+        Ok(Client::new(&site_url(), &load_session()?))
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String, SiteError> {
+        self.get(&format!("/{year}/day/{day}/input"))
+    }
+
+    /// The puzzle description, converted to the markdown aoc-cli writes.
+    pub fn puzzle(&self, year: u16, day: u8) -> Result<String, SiteError> {
+        let html = self.get(&format!("/{year}/day/{day}"))?;
+        Ok(puzzle::from_html(&html))
+    }
+
+    /// Checks that the site accepts the session cookie, by requesting an input that only a
+    /// logged in user can see.
+    pub fn check_session(&self) -> Result<(), SiteError> {
+        let year = calendar::current_event_year(SystemTime::now());
+        self.input(year, calendar::FIRST_DAY).map(|_| ())
+    }
+
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitOutcome, SiteError> {
+        let request = self
+            .agent
+            .post(&format!("{}/{year}/day/{day}/answer", self.base_url))
+            .set("Cookie", &format!("session={}", self.session));
+        let html =
+            read_response(request.send_form(&[("level", &part.to_string()), ("answer", answer)]))?;
+        Ok(SubmitOutcome::classify(&puzzle::from_html(&html)))
+    }
+
+    /// Requests `path` with the session cookie and returns the response body.
+    pub fn get(&self, path: &str) -> Result<String, SiteError> {
+        let request = self
+            .agent
+            .get(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session));
+        read_response(request.call())
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, SiteError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e: io::Error| SiteError::Transport(e.to_string())),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(SiteError::Status {
+                status,
+                failure: classify_status(status, &body),
+                body,
+            })
+        }
+        Err(ureq::Error::Transport(e)) => Err(SiteError::Transport(e.to_string())),
+    }
+}
+
+fn classify_status(status: u16, body: &str) -> Failure {
+    match status {
+        404 if body.contains("unlocks") => Failure::NotUnlocked,
+        404 => Failure::NotFound,
+        400 | 401 | 500 if body.to_lowercase().contains("log in") => Failure::ExpiredSession,
+        429 => Failure::RateLimited,
+        _ => Failure::classify(body),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_submit() {
+        assert_eq!(
+            SubmitOutcome::classify("That's the right answer! You are one gold star closer."),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            SubmitOutcome::classify(
+                "That's not the right answer; your answer is too high. Please wait one minute."
+            ),
+            SubmitOutcome::Incorrect(Some("too high".into()))
+        );
+        assert_eq!(
+            SubmitOutcome::classify("That's not the right answer. If you're stuck, ..."),
+            SubmitOutcome::Incorrect(None)
+        );
+        assert_eq!(
+            SubmitOutcome::classify("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait."),
+            SubmitOutcome::RateLimited(Some("34s".into()))
+        );
+        assert_eq!(
+            SubmitOutcome::classify(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            SubmitOutcome::WrongLevel
+        );
+        assert!(matches!(
+            SubmitOutcome::classify("Hello"),
+            SubmitOutcome::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn test_classify_status() {
+        assert_eq!(
+            classify_status(
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!"
+            ),
+            Failure::NotUnlocked
+        );
+        assert_eq!(classify_status(404, "404 Not Found"), Failure::NotFound);
+        assert_eq!(
+            classify_status(
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+            ),
+            Failure::ExpiredSession
+        );
+        assert_eq!(classify_status(429, ""), Failure::RateLimited);
+    }
+
+    #[test]
+    fn test_parse_session() {
+        assert_eq!(parse_session(" 53616c74 \n"), Ok("53616c74".into()));
+        assert_eq!(parse_session("session=53616c74"), Ok("53616c74".into()));
+        assert!(parse_session("").is_err());
+        assert!(parse_session("53616c74; Path=/").is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_save_session() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        save_session(&path, "53616c74").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "53616c74\n");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
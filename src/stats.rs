@@ -0,0 +1,264 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env,
+    fmt::{Debug, Display},
+    fs, io,
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use crate::calendar;
+
+/// Where the solve timestamps of each day are recorded, as `src/stats/NN.txt`.
+pub const STATS_DIR: &str = "src/stats";
+
+/// Environment variable that opts in to recording when examples pass, so a plain `cargo test`
+/// leaves the tree alone, e.g. `AOC_RECORD_EXAMPLES=1 cargo test --bin 01`.
+pub const RECORD_EXAMPLES_ENV: &str = "AOC_RECORD_EXAMPLES";
+
+/// The example tests of a day run in parallel and would otherwise overwrite each other's records.
+static RECORDING: Mutex<()> = Mutex::new(());
+
+/// Milestones of solving a day. Only the first time each one happens is recorded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    Scaffolded,
+    ExamplePassed(u8),
+    Accepted(u8),
+}
+
+impl Event {
+    const ALL: [Event; 5] = [
+        Event::Scaffolded,
+        Event::ExamplePassed(1),
+        Event::Accepted(1),
+        Event::ExamplePassed(2),
+        Event::Accepted(2),
+    ];
+
+    fn key(&self) -> String {
+        match self {
+            Event::Scaffolded => "scaffolded".into(),
+            Event::ExamplePassed(part) => format!("example_{part}"),
+            Event::Accepted(part) => format!("accepted_{part}"),
+        }
+    }
+}
+
+/// When the milestones of a day happened, stored as the `key = value` lines of its `Display`
+/// impl.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timings {
+    pub year: u16,
+    pub day: u8,
+    events: Vec<(Event, SystemTime)>,
+}
+
+impl Timings {
+    pub fn new(year: u16, day: u8) -> Self {
+        Timings {
+            year,
+            day,
+            events: vec![],
+        }
+    }
+
+    pub fn parse(day: u8, val: &str) -> Option<Self> {
+        let field = |key: &str| {
+            val.lines().find_map(|line| {
+                let (k, v) = line.split_once('=')?;
+                (k.trim() == key).then(|| v.trim().to_string())
+            })
+        };
+
+        let mut timings = Timings::new(field("year")?.parse().ok()?, day);
+        for event in Event::ALL {
+            if let Some(time) = field(&event.key()).and_then(|v| calendar::parse_utc(&v)) {
+                timings.events.push((event, time));
+            }
+        }
+        Some(timings)
+    }
+
+    pub fn get(&self, event: Event) -> Option<SystemTime> {
+        self.events
+            .iter()
+            .find_map(|&(e, time)| (e == event).then_some(time))
+    }
+
+    /// Records `event` unless it happened before. Returns whether it was recorded.
+    pub fn record(&mut self, event: Event, time: SystemTime) -> bool {
+        if self.get(event).is_some() {
+            return false;
+        }
+        self.events.push((event, time));
+        true
+    }
+
+    /// When work on part one started: at unlock, or at scaffolding if that came later.
+    pub fn started(&self) -> SystemTime {
+        let unlock = calendar::unlock_time(self.year, self.day);
+        self.get(Event::Scaffolded)
+            .map_or(unlock, |s| s.max(unlock))
+    }
+
+    /// When work on `part` started, the acceptance of part one for part two.
+    fn part_started(&self, part: u8) -> Option<SystemTime> {
+        match part {
+            1 => Some(self.started()),
+            _ => self.get(Event::Accepted(1)),
+        }
+    }
+
+    /// How long `part` took from starting it until its answer was accepted.
+    pub fn time_to_solve(&self, part: u8) -> Option<Duration> {
+        elapsed(self.part_started(part)?, self.get(Event::Accepted(part))?)
+    }
+
+    /// How long the example of `part` took to pass after starting it.
+    pub fn time_to_example(&self, part: u8) -> Option<Duration> {
+        elapsed(
+            self.part_started(part)?,
+            self.get(Event::ExamplePassed(part))?,
+        )
+    }
+
+    /// How long both parts took together.
+    pub fn total(&self) -> Option<Duration> {
+        elapsed(self.started(), self.get(Event::Accepted(2))?)
+    }
+
+    /// How long after unlock work started, zero for days scaffolded ahead of time.
+    pub fn start_delay(&self) -> Duration {
+        elapsed(calendar::unlock_time(self.year, self.day), self.started()).unwrap_or_default()
+    }
+}
+
+fn elapsed(from: SystemTime, to: SystemTime) -> Option<Duration> {
+    to.duration_since(from).ok()
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "year = {}", self.year)?;
+        for event in Event::ALL {
+            if let Some(time) = self.get(event) {
+                writeln!(f, "{} = {}", event.key(), calendar::format_utc(time))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn get_path(day: u8) -> String {
+    format!("{STATS_DIR}/{day:02}.txt")
+}
+
+pub fn read(day: u8) -> Option<Timings> {
+    Timings::parse(day, &fs::read_to_string(get_path(day)).ok()?)
+}
+
+pub fn write(timings: &Timings) -> io::Result<()> {
+    fs::create_dir_all(STATS_DIR)?;
+    fs::write(get_path(timings.day), timings.to_string())
+}
+
+/// Records that `event` of `day` happened now, if it has not happened before. The timings of a
+/// day belong to a single event, so recording for another `year` is an error.
+pub fn record(year: u16, day: u8, event: Event) -> io::Result<()> {
+    let mut timings = match read(day) {
+        Some(timings) if timings.year != year => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "\"{}\" holds the solve times of {}, not {year}",
+                    get_path(day),
+                    timings.year
+                ),
+            ))
+        }
+        Some(timings) => timings,
+        None => Timings::new(year, day),
+    };
+    if timings.record(event, SystemTime::now()) {
+        write(&timings)?;
+    }
+    Ok(())
+}
+
+/// Asserts that a solution solves the example of `part`, like `assert_eq!`.
+///
+/// With [`RECORD_EXAMPLES_ENV`] set, the first time a day scaffolded with timings passes with an
+/// expected answer, that time is recorded. Days without a timings file, e.g. solved before timings
+/// existed, are left alone.
+#[track_caller]
+pub fn assert_example<T: PartialEq + Debug>(
+    day: u8,
+    part: u8,
+    actual: Option<T>,
+    expected: Option<T>,
+) {
+    assert_eq!(actual, expected);
+
+    if expected.is_none() || env::var_os(RECORD_EXAMPLES_ENV).is_none() {
+        return;
+    }
+    let _guard = RECORDING.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(mut timings) = read(day) {
+        if timings.record(Event::ExamplePassed(part), SystemTime::now()) {
+            write(&timings).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    // day 1 of 2022 unlocked at 1669870800.
+    const TIMINGS: &str = "year = 2022
+scaffolded = 2022-12-01T05:02:00Z
+example_1 = 2022-12-01T05:10:00Z
+accepted_1 = 2022-12-01T05:12:00Z
+accepted_2 = 2022-12-01T05:20:30Z
+";
+
+    #[test]
+    fn test_timings_roundtrip() {
+        let timings = Timings::parse(1, TIMINGS).unwrap();
+        assert_eq!(timings.get(Event::Scaffolded), Some(at(1_669_870_920)));
+        assert_eq!(timings.get(Event::ExamplePassed(2)), None);
+        assert_eq!(timings.to_string(), TIMINGS);
+        assert_eq!(Timings::parse(1, "scaffolded = 2022-12-01T05:02:00Z"), None);
+    }
+
+    #[test]
+    fn test_time_to_solve() {
+        let timings = Timings::parse(1, TIMINGS).unwrap();
+        assert_eq!(timings.start_delay(), Duration::from_secs(120));
+        assert_eq!(timings.time_to_example(1), Some(Duration::from_secs(480)));
+        assert_eq!(timings.time_to_solve(1), Some(Duration::from_secs(600)));
+        assert_eq!(timings.time_to_solve(2), Some(Duration::from_secs(510)));
+        assert_eq!(timings.time_to_example(2), None);
+        assert_eq!(timings.total(), Some(Duration::from_secs(1110)));
+    }
+
+    #[test]
+    fn test_started() {
+        // scaffolded ahead of the unlock, the clock starts at unlock.
+        let mut timings = Timings::new(2022, 1);
+        assert!(timings.record(Event::Scaffolded, at(1_669_800_000)));
+        assert!(!timings.record(Event::Scaffolded, at(1_669_870_920)));
+        assert_eq!(timings.started(), at(1_669_870_800));
+        assert_eq!(timings.start_delay(), Duration::ZERO);
+        assert_eq!(timings.time_to_solve(1), None);
+    }
+}
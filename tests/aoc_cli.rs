@@ -0,0 +1,183 @@
+//! Runs the commands through the aoc-cli backend, with a script standing in for aoc-cli that
+//! records how it was called and answers like the real one.
+#![cfg(unix)]
+
+mod working_dir;
+
+use advent_of_code::aoc_cli::{self, AocCli, AocCliError, Backend, Failure, Version};
+use advent_of_code::provenance::{self, Status};
+use advent_of_code::site::SubmitOutcome;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use working_dir::TempWorkingDir;
+
+const YEAR: u16 = 2022;
+const INPUT: &str = "1000\n2000\n";
+const PUZZLE: &str = "## --- Day 1: Fake Puzzle ---\n";
+
+/// Behaves like aoc-cli 0.12 for days 1 and 2, later days are still locked.
+const FAKE_AOC: &str = r#"#!/bin/sh
+echo "$@" >> "$(dirname "$0")/calls.log"
+if [ "$1" = "-V" ]; then
+    echo "aoc-cli 0.12.0"
+    exit 0
+fi
+
+command=$1
+shift
+while [ $# -gt 0 ]; do
+    case $1 in
+        --input-file) input=$2; shift ;;
+        --puzzle-file) puzzle=$2; shift ;;
+        --session-file) shift ;;
+        --day) day=$2; shift ;;
+        --year) year=$2; shift ;;
+        --*) ;;
+        *) if [ -z "$part" ]; then part=$1; else answer=$1; fi ;;
+    esac
+    shift
+done
+
+if [ "$day" -gt 2 ]; then
+    echo "error: Puzzle $day of $year is still locked" >&2
+    exit 1
+fi
+
+case $command in
+    download)
+        if [ -n "$input" ]; then printf '1000\n2000\n' > "$input"; fi
+        printf '## --- Day 1: Fake Puzzle ---\n' > "$puzzle"
+        ;;
+    read) printf '## --- Day 1: Fake Puzzle ---\n' ;;
+    submit)
+        if [ "$answer" = "3000" ]; then
+            echo "That's the right answer! You are one gold star closer."
+        else
+            echo "That's not the right answer; your answer is too high."
+        fi
+        ;;
+esac
+"#;
+
+/// Writes the fake aoc-cli to `dir` and returns a backend calling it.
+fn fake_aoc_cli(dir: &TempWorkingDir) -> Backend {
+    let path = dir.path.join("aoc");
+    fs::write(&path, FAKE_AOC).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    Backend::AocCli(aoc_cli::check_program(path.to_str().unwrap()).unwrap())
+}
+
+/// The arguments of each call of the fake aoc-cli.
+fn calls(dir: &TempWorkingDir) -> Vec<String> {
+    fs::read_to_string(dir.path.join("calls.log"))
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn test_check() {
+    let dir = TempWorkingDir::new("aoc-cli-check");
+    let Backend::AocCli(AocCli { version, .. }) = fake_aoc_cli(&dir) else {
+        panic!("expected the aoc-cli backend");
+    };
+    assert_eq!(version, Version::new(0, 12, 0));
+    assert_eq!(calls(&dir), ["-V"]);
+
+    assert!(matches!(
+        aoc_cli::check_program("./missing-aoc"),
+        Err(AocCliError::CommandNotFound(_))
+    ));
+}
+
+#[test]
+fn test_download() {
+    let dir = TempWorkingDir::new("aoc-cli-download");
+    let backend = fake_aoc_cli(&dir);
+
+    aoc_cli::download(&backend, 1, Some(YEAR), false).unwrap();
+    assert_eq!(
+        calls(&dir)[1],
+        "download --overwrite --input-file src/inputs/01.txt --puzzle-file src/puzzles/01.md --year 2022 --day 1"
+    );
+    assert_eq!(
+        fs::read_to_string(aoc_cli::get_input_path(1)).unwrap(),
+        INPUT
+    );
+    assert_eq!(
+        fs::read_to_string(aoc_cli::get_puzzle_path(1)).unwrap(),
+        PUZZLE
+    );
+    assert_eq!(provenance::status(1), Status::Unmodified);
+
+    // an edited input is not passed to aoc-cli to overwrite without --force.
+    fs::write(aoc_cli::get_input_path(1), "edited").unwrap();
+    assert!(matches!(
+        aoc_cli::download(&backend, 1, Some(YEAR), false),
+        Err(AocCliError::ModifiedInput { .. })
+    ));
+    assert_eq!(calls(&dir).len(), 2);
+
+    aoc_cli::refresh_puzzle(&backend, 1, Some(YEAR)).unwrap();
+    assert_eq!(
+        calls(&dir)[2],
+        "download --overwrite --puzzle-only --puzzle-file src/puzzles/01.md --year 2022 --day 1"
+    );
+    assert_eq!(
+        fs::read_to_string(aoc_cli::get_input_path(1)).unwrap(),
+        "edited"
+    );
+
+    let err = aoc_cli::download(&backend, 3, Some(YEAR), false).unwrap_err();
+    assert_eq!(err.failure(), Some(Failure::NotUnlocked));
+    assert!(err.hint().is_some());
+}
+
+#[test]
+fn test_read() {
+    let dir = TempWorkingDir::new("aoc-cli-read");
+    let backend = fake_aoc_cli(&dir);
+
+    assert!(aoc_cli::read(&backend, 1, Some(YEAR)).is_ok());
+    assert!(aoc_cli::read(&backend, 3, Some(YEAR)).is_err());
+    assert_eq!(
+        calls(&dir)[1..],
+        ["read --year 2022 --day 1", "read --year 2022 --day 3"]
+    );
+}
+
+#[test]
+fn test_submit() {
+    let dir = TempWorkingDir::new("aoc-cli-submit");
+    let backend = fake_aoc_cli(&dir);
+
+    assert_eq!(
+        aoc_cli::submit(&backend, 1, Some(YEAR), 1, "99999").unwrap(),
+        SubmitOutcome::Incorrect(Some("too high".into()))
+    );
+    assert_eq!(
+        aoc_cli::submit(&backend, 1, Some(YEAR), 1, "3000").unwrap(),
+        SubmitOutcome::Correct
+    );
+    assert_eq!(calls(&dir)[2], "submit --year 2022 --day 1 1 3000");
+}
+
+#[test]
+fn test_check_session() {
+    let dir = TempWorkingDir::new("aoc-cli-check-session");
+    let backend = fake_aoc_cli(&dir);
+
+    aoc_cli::check_session(&backend).unwrap();
+    let call = &calls(&dir)[1];
+    assert!(call.starts_with("download --overwrite --input-file "));
+
+    // the input is only downloaded to scratch files, which are removed again.
+    let args: Vec<&str> = call.split(' ').collect();
+    for file in ["--input-file", "--puzzle-file"] {
+        let path = args[args.iter().position(|&arg| arg == file).unwrap() + 1];
+        assert!(!Path::new(path).exists());
+    }
+    assert!(!Path::new(&aoc_cli::get_input_path(1)).exists());
+}
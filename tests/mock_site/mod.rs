@@ -0,0 +1,203 @@
+//! A stand-in for adventofcode.com that serves canned puzzles, inputs, answer responses and
+//! leaderboards over localhost, so the site-facing code paths can be tested offline.
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub const SESSION: &str = "test-session";
+pub const YEAR: u16 = 2022;
+/// Days after this one are not unlocked yet.
+pub const LAST_UNLOCKED_DAY: u8 = 2;
+pub const LEADERBOARD_ID: u64 = 1234;
+
+pub const INPUT: &str = "1000\n2000\n\n3000\n";
+pub const ANSWERS: [&str; 2] = ["3000", "6000"];
+
+pub const LEADERBOARD: &str = r#"{"event":"2022","owner_id":1,"members":{"1":{"id":1,"name":"Alice","stars":3,"local_score":10,"global_score":0,"last_star_ts":1669960000,"completion_day_level":{"1":{"1":{"get_star_ts":1669872000,"star_index":0},"2":{"get_star_ts":1669872300,"star_index":1}},"2":{"1":{"get_star_ts":1669958500,"star_index":2}}}},"2":{"id":2,"name":null,"stars":2,"local_score":4,"global_score":0,"last_star_ts":1669880000,"completion_day_level":{"1":{"1":{"get_star_ts":1669875600,"star_index":0},"2":{"get_star_ts":1669880000,"star_index":1}}}}}}"#;
+
+#[derive(Default)]
+struct State {
+    /// Parts solved per day.
+    solved: HashMap<u8, u8>,
+    /// Set after a wrong answer, like the real site does for a while.
+    rate_limited: bool,
+}
+
+pub struct MockSite {
+    pub url: String,
+}
+
+impl MockSite {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = Arc::clone(&state);
+                thread::spawn(move || handle(stream, &state));
+            }
+        });
+
+        MockSite { url }
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    cookie: Option<String>,
+    body: String,
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut request_line = line.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+
+    let mut cookie = None;
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        reader.read_line(&mut line).ok()?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header.split_once(':')?;
+        match name.to_lowercase().as_str() {
+            "cookie" => cookie = Some(value.trim().to_string()),
+            "content-length" => content_length = value.trim().parse().ok()?,
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(Request {
+        method,
+        path,
+        cookie,
+        body: String::from_utf8(body).ok()?,
+    })
+}
+
+fn handle(mut stream: TcpStream, state: &Mutex<State>) {
+    let Some(request) = read_request(&stream) else {
+        return;
+    };
+
+    let (status, body) = respond(&request, &mut state.lock().unwrap());
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+    let response = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).ok();
+}
+
+fn respond(request: &Request, state: &mut State) -> (u16, String) {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let logged_in = request.cookie.as_deref() == Some(&format!("session={SESSION}"));
+
+    match (request.method.as_str(), &segments[..]) {
+        ("GET", [_, "leaderboard", "private", "view", file]) => {
+            if !logged_in {
+                return (400, "Please log in to view private leaderboards.".into());
+            }
+            match file.strip_suffix(".json") {
+                Some(id) if id == LEADERBOARD_ID.to_string() => (200, LEADERBOARD.into()),
+                _ => (404, "404 Not Found".into()),
+            }
+        }
+        (method, [_, "day", day, rest @ ..]) => {
+            let Ok(day) = day.parse::<u8>() else {
+                return (404, "404 Not Found".into());
+            };
+            if day > LAST_UNLOCKED_DAY {
+                return (
+                    404,
+                    "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.".into(),
+                );
+            }
+
+            match (method, rest) {
+                ("GET", []) => (
+                    200,
+                    puzzle(day, state.solved.get(&day).copied().unwrap_or(0)),
+                ),
+                ("GET", ["input"]) if logged_in => (200, INPUT.into()),
+                ("GET", ["input"]) => (
+                    400,
+                    "Puzzle inputs differ by user.  Please log in to get your puzzle input.".into(),
+                ),
+                ("POST", ["answer"]) if logged_in => (200, answer(day, &request.body, state)),
+                _ => (404, "404 Not Found".into()),
+            }
+        }
+        _ => (404, "404 Not Found".into()),
+    }
+}
+
+fn puzzle(day: u8, solved: u8) -> String {
+    let mut html = format!(
+        "<main>\n<article class=\"day-desc\"><h2>--- Day {day}: Mock Puzzle ---</h2><p>For example, consider this list:</p>\n<pre><code>1000\n2000\n</code></pre>\n<p>Find the <em>largest</em> number.</p>\n</article>\n"
+    );
+    if solved >= 1 {
+        html.push_str(&format!(
+            "<p>Your puzzle answer was <code>{}</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Now add them up.</p>\n</article>\n",
+            ANSWERS[0]
+        ));
+    }
+    if solved >= 2 {
+        html.push_str(&format!(
+            "<p>Your puzzle answer was <code>{}</code>.</p>\n",
+            ANSWERS[1]
+        ));
+    }
+    html.push_str("<form method=\"post\"><p>Answer: <input type=\"text\" name=\"answer\"/></p></form>\n</main>");
+    html
+}
+
+fn answer(day: u8, body: &str, state: &mut State) -> String {
+    let field = |key: &str| {
+        body.split('&')
+            .find_map(|pair| pair.strip_prefix(&format!("{key}=")))
+            .unwrap_or_default()
+            .to_string()
+    };
+    let level: u8 = field("level").parse().unwrap_or_default();
+    let answer = field("answer");
+
+    let message = if state.rate_limited {
+        "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 42s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a>".to_string()
+    } else if level != state.solved.get(&day).copied().unwrap_or(0) + 1 {
+        "You don't seem to be solving the right level.  Did you already complete it?".to_string()
+    } else if answer == ANSWERS[usize::from(level) - 1] {
+        *state.solved.entry(day).or_default() += 1;
+        "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to collecting enough star fruit.".to_string()
+    } else {
+        state.rate_limited = true;
+        let expected: i64 = ANSWERS[usize::from(level) - 1].parse().unwrap();
+        let hint = match answer.parse::<i64>() {
+            Ok(answer) if answer > expected => "; your answer is too high",
+            Ok(_) => "; your answer is too low",
+            Err(_) => "",
+        };
+        format!("That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data.")
+    };
+
+    format!("<main>\n<article><p>{message}</p></article>\n</main>")
+}
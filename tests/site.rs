@@ -0,0 +1,186 @@
+mod mock_site;
+mod working_dir;
+
+use advent_of_code::aoc_cli::{self, AocCliError, Backend, Failure};
+use advent_of_code::leaderboard::{self, Leaderboard};
+use advent_of_code::provenance::{self, Status};
+use advent_of_code::puzzle::{self, PuzzleInfo};
+use advent_of_code::site::{Client, SubmitOutcome};
+use mock_site::{MockSite, ANSWERS, INPUT, LAST_UNLOCKED_DAY, SESSION, YEAR};
+use std::fs;
+use std::time::{Duration, SystemTime};
+use working_dir::TempWorkingDir;
+
+fn site() -> (MockSite, Backend) {
+    let site = MockSite::start();
+    let backend = Backend::Site(Client::new(&site.url, SESSION));
+    (site, backend)
+}
+
+#[test]
+fn test_fetch_input_and_puzzle() {
+    let site = MockSite::start();
+    let client = Client::new(&site.url, SESSION);
+
+    assert_eq!(client.input(YEAR, 1).unwrap(), INPUT);
+
+    let markdown = client.puzzle(YEAR, 1).unwrap();
+    let info = PuzzleInfo::parse(1, &markdown);
+    assert_eq!(info.heading(), "Day 01: Mock Puzzle");
+    assert!(!info.has_part_two);
+    assert!(info.answers.is_empty());
+    assert_eq!(puzzle::example(&markdown), Some("1000\n2000\n".into()));
+}
+
+#[test]
+fn test_fetch_failures() {
+    let site = MockSite::start();
+
+    let client = Client::new(&site.url, "expired");
+    assert_eq!(
+        client.input(YEAR, 1).unwrap_err().failure(),
+        Failure::ExpiredSession
+    );
+
+    let client = Client::new(&site.url, SESSION);
+    assert_eq!(
+        client
+            .puzzle(YEAR, LAST_UNLOCKED_DAY + 1)
+            .unwrap_err()
+            .failure(),
+        Failure::NotUnlocked
+    );
+}
+
+#[test]
+fn test_check_session() {
+    let site = MockSite::start();
+
+    assert!(Client::new(&site.url, SESSION).check_session().is_ok());
+    assert_eq!(
+        Client::new(&site.url, "expired")
+            .check_session()
+            .unwrap_err()
+            .failure(),
+        Failure::ExpiredSession
+    );
+}
+
+#[test]
+fn test_download() {
+    let _dir = TempWorkingDir::new("download");
+    let (_site, backend) = site();
+
+    aoc_cli::download(&backend, 1, Some(YEAR), false).unwrap();
+    assert_eq!(fs::read_to_string("src/inputs/01.txt").unwrap(), INPUT);
+    assert_eq!(puzzle::info(1).unwrap().heading(), "Day 01: Mock Puzzle");
+    assert_eq!(provenance::status(1), Status::Unmodified);
+    assert!(aoc_cli::is_downloaded(1));
+
+    // an edited input is not overwritten without --force.
+    fs::write("src/inputs/01.txt", "edited").unwrap();
+    assert!(matches!(
+        aoc_cli::download(&backend, 1, Some(YEAR), false),
+        Err(AocCliError::ModifiedInput { .. })
+    ));
+    aoc_cli::download(&backend, 1, Some(YEAR), true).unwrap();
+    assert_eq!(fs::read_to_string("src/inputs/01.txt").unwrap(), INPUT);
+
+    // one without metadata, e.g. from before it was recorded, is replaced.
+    fs::write("src/inputs/01.txt", "old").unwrap();
+    fs::remove_file(provenance::get_metadata_path(1)).unwrap();
+    assert_eq!(provenance::status(1), Status::Untracked);
+    aoc_cli::download(&backend, 1, Some(YEAR), false).unwrap();
+    assert_eq!(provenance::status(1), Status::Unmodified);
+
+    let err = aoc_cli::download(&backend, LAST_UNLOCKED_DAY + 1, Some(YEAR), false).unwrap_err();
+    assert_eq!(err.failure(), Some(Failure::NotUnlocked));
+    assert!(err.hint().is_some());
+}
+
+#[test]
+fn test_read() {
+    let _dir = TempWorkingDir::new("read");
+    let (_site, backend) = site();
+    assert!(aoc_cli::read(&backend, 1, Some(YEAR)).is_ok());
+    assert!(aoc_cli::read(&backend, LAST_UNLOCKED_DAY + 1, Some(YEAR)).is_err());
+}
+
+#[test]
+fn test_submit_correct() {
+    let _dir = TempWorkingDir::new("submit");
+    let (_site, backend) = site();
+
+    assert_eq!(
+        aoc_cli::submit(&backend, 1, Some(YEAR), 2, ANSWERS[1]).unwrap(),
+        SubmitOutcome::WrongLevel
+    );
+    assert_eq!(
+        aoc_cli::submit(&backend, 1, Some(YEAR), 1, ANSWERS[0]).unwrap(),
+        SubmitOutcome::Correct
+    );
+
+    // the refreshed puzzle records the answer and includes part two.
+    aoc_cli::refresh_puzzle(&backend, 1, Some(YEAR)).unwrap();
+    let info = puzzle::info(1).unwrap();
+    assert_eq!(info.answers, vec![ANSWERS[0]]);
+    assert!(info.has_part_two);
+
+    assert_eq!(
+        aoc_cli::submit(&backend, 1, Some(YEAR), 1, ANSWERS[0]).unwrap(),
+        SubmitOutcome::WrongLevel
+    );
+}
+
+#[test]
+fn test_submit_incorrect() {
+    let _dir = TempWorkingDir::new("submit-incorrect");
+    let (_site, backend) = site();
+
+    assert_eq!(
+        aoc_cli::submit(&backend, 1, Some(YEAR), 1, "99999").unwrap(),
+        SubmitOutcome::Incorrect(Some("too high".into()))
+    );
+    assert_eq!(
+        aoc_cli::submit(&backend, 1, Some(YEAR), 1, ANSWERS[0]).unwrap(),
+        SubmitOutcome::RateLimited(Some("42s".into()))
+    );
+}
+
+#[test]
+fn test_leaderboard() {
+    let _dir = TempWorkingDir::new("leaderboard");
+    let site = MockSite::start();
+    let id = mock_site::LEADERBOARD_ID;
+
+    let json = leaderboard::fetch(&Client::new(&site.url, SESSION), YEAR, id).unwrap();
+    let ranked: Vec<String> = Leaderboard::parse(&json)
+        .unwrap()
+        .ranked()
+        .iter()
+        .map(|member| member.display_name())
+        .collect();
+    assert_eq!(ranked, ["Alice", "(anonymous user #2)"]);
+
+    // a copy is only kept to compare scores against once it is from an earlier day.
+    let now = SystemTime::now();
+    let cache_fetched_at = |time: SystemTime| {
+        let file = fs::File::options()
+            .write(true)
+            .open(leaderboard::get_cache_path(YEAR, id))
+            .unwrap();
+        file.set_modified(time).unwrap();
+    };
+    leaderboard::write_cache(YEAR, id, "{}", now).unwrap();
+    cache_fetched_at(now);
+    leaderboard::write_cache(YEAR, id, &json, now).unwrap();
+    assert_eq!(leaderboard::read_cache(YEAR, id).unwrap().0, json);
+    assert!(leaderboard::read_previous(YEAR, id).is_none());
+
+    cache_fetched_at(now - Duration::from_secs(24 * 60 * 60));
+    leaderboard::write_cache(YEAR, id, &json, now).unwrap();
+    assert!(leaderboard::read_previous(YEAR, id).is_some());
+
+    let err = leaderboard::fetch(&Client::new(&site.url, "expired"), YEAR, id).unwrap_err();
+    assert_eq!(err.failure(), Failure::ExpiredSession);
+}
@@ -0,0 +1,39 @@
+//! A temporary working directory for tests of commands that read and write files relative to
+//! it, like the inputs and puzzles of the download command.
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+use std::{env, fs, process};
+
+/// The working directory is shared by all tests in a binary.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Switches to a fresh, empty directory. Dropping it switches back and removes the directory.
+pub struct TempWorkingDir {
+    pub path: PathBuf,
+    previous: PathBuf,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl TempWorkingDir {
+    pub fn new(name: &str) -> Self {
+        let lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let path = env::temp_dir().join(format!("aoc-test-{}-{name}", process::id()));
+        fs::remove_dir_all(&path).ok();
+        fs::create_dir_all(&path).unwrap();
+        let previous = env::current_dir().unwrap();
+        env::set_current_dir(&path).unwrap();
+
+        TempWorkingDir {
+            path,
+            previous,
+            _lock: lock,
+        }
+    }
+}
+
+impl Drop for TempWorkingDir {
+    fn drop(&mut self) {
+        env::set_current_dir(&self.previous).ok();
+        fs::remove_dir_all(&self.path).ok();
+    }
+}